@@ -0,0 +1,12 @@
+#![no_main]
+
+use fuel_abi_types::abi::{full_program::FullProgramABI, unified_program::parse_and_unify};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(abi) = std::str::from_utf8(data) {
+        if let Ok(unified) = parse_and_unify(abi) {
+            let _ = FullProgramABI::try_from(&unified);
+        }
+    }
+});
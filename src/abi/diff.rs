@@ -0,0 +1,550 @@
+//! Structured comparison of two [`FullProgramABI`]s, e.g. to detect breaking changes
+//! between releases of a program.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{Display, Formatter},
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::full_program::{
+    FullABIFunction, FullProgramABI, FullTypeApplication, FullTypeDeclaration,
+};
+
+/// The differences between an old and a new version of an ABI, see
+/// [`FullProgramABI::diff`].
+///
+/// Functions are identified by their name, which is also what selects them in
+/// calls made with the encoding version 1.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AbiDiff {
+    pub added_functions: Vec<String>,
+    pub removed_functions: Vec<String>,
+    pub changed_functions: Vec<FunctionChange>,
+    pub added_types: Vec<FullTypeDeclaration>,
+    pub removed_types: Vec<FullTypeDeclaration>,
+    pub added_configurables: Vec<String>,
+    pub removed_configurables: Vec<String>,
+    pub changed_configurables: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FunctionChange {
+    pub name: String,
+    pub kind: FunctionChangeKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FunctionChangeKind {
+    /// The inputs or the output changed. Calls encoded against the old ABI will no
+    /// longer work.
+    Signature,
+    /// Only the attributes, such as doc comments, or the names of parameters changed.
+    Attributes,
+}
+
+/// A change that breaks callers built against the old version of an ABI.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BreakingChange {
+    /// The function no longer exists, either because it was removed or renamed.
+    RemovedFunction(String),
+    /// The inputs or output of the function changed.
+    ChangedSignature(String),
+}
+
+/// A change to the configurables of a program, see [`FullProgramABI::configurable_diff`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConfigurableChange {
+    Added(String),
+    Removed(String),
+    /// The configurable's value moved within the program binary.
+    Offset {
+        name: String,
+        old: u64,
+        new: u64,
+    },
+    /// The configurable's type changed, so values stored for it must be converted.
+    Type {
+        name: String,
+        old: Box<FullTypeApplication>,
+        new: Box<FullTypeApplication>,
+    },
+    /// The configurable switched between storing its value at its offset and storing a
+    /// pointer to it.
+    Indirect {
+        name: String,
+        old: bool,
+        new: bool,
+    },
+}
+
+impl AbiDiff {
+    /// Returns whether the two ABIs compared are the same.
+    pub fn is_empty(&self) -> bool {
+        self.added_functions.is_empty()
+            && self.removed_functions.is_empty()
+            && self.changed_functions.is_empty()
+            && self.added_types.is_empty()
+            && self.removed_types.is_empty()
+            && self.added_configurables.is_empty()
+            && self.removed_configurables.is_empty()
+            && self.changed_configurables.is_empty()
+    }
+
+    /// Returns the changes that break existing callers. Added functions and changes
+    /// to attributes only are not considered breaking.
+    pub fn breaking_changes(&self) -> Vec<BreakingChange> {
+        let removed = self
+            .removed_functions
+            .iter()
+            .cloned()
+            .map(BreakingChange::RemovedFunction);
+
+        let changed = self
+            .changed_functions
+            .iter()
+            .filter(|change| change.kind == FunctionChangeKind::Signature)
+            .map(|change| BreakingChange::ChangedSignature(change.name.clone()));
+
+        removed.chain(changed).collect()
+    }
+}
+
+/// Lists the differences one per line, prefixed with `+` for additions, `-` for
+/// removals and `~` for changes.
+impl Display for AbiDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "no changes");
+        }
+
+        for name in &self.added_functions {
+            writeln!(f, "+ fn {name}")?;
+        }
+        for name in &self.removed_functions {
+            writeln!(f, "- fn {name}")?;
+        }
+        for change in &self.changed_functions {
+            let kind = match change.kind {
+                FunctionChangeKind::Signature => "signature",
+                FunctionChangeKind::Attributes => "attributes",
+            };
+            writeln!(f, "~ fn {} ({kind})", change.name)?;
+        }
+        for ttype in &self.added_types {
+            writeln!(f, "+ type {}", ttype.type_field)?;
+        }
+        for ttype in &self.removed_types {
+            writeln!(f, "- type {}", ttype.type_field)?;
+        }
+        for name in &self.added_configurables {
+            writeln!(f, "+ configurable {name}")?;
+        }
+        for name in &self.removed_configurables {
+            writeln!(f, "- configurable {name}")?;
+        }
+        for name in &self.changed_configurables {
+            writeln!(f, "~ configurable {name}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FullProgramABI {
+    /// Compares `self`, the old version of an ABI, to `other`, the new one.
+    pub fn diff(&self, other: &FullProgramABI) -> AbiDiff {
+        let (added_functions, removed_functions, common_functions) =
+            diff_by_name(&self.functions, &other.functions, |f| f.name());
+
+        let changed_functions = common_functions
+            .into_iter()
+            .filter(|(old, new)| old != new)
+            .map(|(old, new)| FunctionChange {
+                name: old.name().to_string(),
+                kind: if has_same_signature(old, new) {
+                    FunctionChangeKind::Attributes
+                } else {
+                    FunctionChangeKind::Signature
+                },
+            })
+            .collect();
+
+        let (added_configurables, removed_configurables, common_configurables) =
+            diff_by_name(&self.configurables, &other.configurables, |c| &c.name);
+
+        let changed_configurables = common_configurables
+            .into_iter()
+            .filter(|(old, new)| old != new)
+            .map(|(old, _)| old.name.clone())
+            .collect();
+
+        AbiDiff {
+            added_functions,
+            removed_functions,
+            changed_functions,
+            added_types: missing_from(&other.types, &self.types),
+            removed_types: missing_from(&self.types, &other.types),
+            added_configurables,
+            removed_configurables,
+            changed_configurables,
+        }
+    }
+
+    /// Compares the configurables of `self`, the old version of a program, to those of
+    /// `other`, the new one. Configurables are matched by name and a configurable that
+    /// changed in several ways gets a change reported for each of them. Removals and
+    /// changes come first, in the order of `self`, followed by additions in the order
+    /// of `other`.
+    pub fn configurable_diff(&self, other: &FullProgramABI) -> Vec<ConfigurableChange> {
+        let (added, removed, common) =
+            diff_by_name(&self.configurables, &other.configurables, |c| &c.name);
+
+        let changed = common.into_iter().flat_map(|(old, new)| {
+            let offset = (old.offset != new.offset).then(|| ConfigurableChange::Offset {
+                name: old.name.clone(),
+                old: old.offset,
+                new: new.offset,
+            });
+            let ttype = (old.application != new.application).then(|| ConfigurableChange::Type {
+                name: old.name.clone(),
+                old: Box::new(old.application.clone()),
+                new: Box::new(new.application.clone()),
+            });
+            let indirect = (old.indirect != new.indirect).then(|| ConfigurableChange::Indirect {
+                name: old.name.clone(),
+                old: old.indirect,
+                new: new.indirect,
+            });
+
+            offset.into_iter().chain(ttype).chain(indirect)
+        });
+
+        removed
+            .into_iter()
+            .map(ConfigurableChange::Removed)
+            .chain(changed)
+            .chain(added.into_iter().map(ConfigurableChange::Added))
+            .collect()
+    }
+}
+
+/// Argument names aren't part of the encoding, so renaming a parameter keeps the
+/// signature intact.
+fn has_same_signature(old: &FullABIFunction, new: &FullABIFunction) -> bool {
+    old.inputs().len() == new.inputs().len()
+        && old
+            .inputs()
+            .iter()
+            .zip(new.inputs())
+            .chain([(old.output(), new.output())])
+            .all(|(old, new)| has_same_type(old, new))
+}
+
+fn has_same_type(old: &FullTypeApplication, new: &FullTypeApplication) -> bool {
+    old.type_decl == new.type_decl && old.type_arguments == new.type_arguments
+}
+
+/// Returns the names only found in `new`, the names only found in `old` and the pairs
+/// of items found in both.
+fn diff_by_name<'a, T>(
+    old: &'a [T],
+    new: &'a [T],
+    name: impl Fn(&T) -> &str,
+) -> (Vec<String>, Vec<String>, Vec<(&'a T, &'a T)>) {
+    let old_by_name: HashMap<_, _> = old.iter().map(|item| (name(item), item)).collect();
+    let new_by_name: HashMap<_, _> = new.iter().map(|item| (name(item), item)).collect();
+
+    let added = new
+        .iter()
+        .map(&name)
+        .filter(|item_name| !old_by_name.contains_key(item_name))
+        .map(String::from)
+        .collect();
+
+    let removed = old
+        .iter()
+        .map(&name)
+        .filter(|item_name| !new_by_name.contains_key(item_name))
+        .map(String::from)
+        .collect();
+
+    let common = old
+        .iter()
+        .filter_map(|item| Some((item, *new_by_name.get(name(item))?)))
+        .collect();
+
+    (added, removed, common)
+}
+
+/// Returns the types in `types` that can't be found in `others`.
+fn missing_from(
+    types: &[FullTypeDeclaration],
+    others: &[FullTypeDeclaration],
+) -> Vec<FullTypeDeclaration> {
+    let others: HashSet<_> = others.iter().collect();
+
+    types
+        .iter()
+        .filter(|ttype| !others.contains(ttype))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        abi::{full_program::FullConfigurable, program::Attribute},
+        test_utils::{appl, decl, full_abi},
+    };
+
+    fn type_decl(type_field: &str) -> FullTypeDeclaration {
+        decl(type_field, vec![])
+    }
+
+    fn application(type_field: &str) -> FullTypeApplication {
+        appl("", type_decl(type_field))
+    }
+
+    fn function(name: &str, input: &str, doc: &str) -> FullABIFunction {
+        FullABIFunction::new(
+            name.to_string(),
+            vec![application(input)],
+            application("()"),
+            vec![Attribute {
+                name: "doc-comment".to_string(),
+                arguments: vec![doc.to_string()],
+            }],
+        )
+        .unwrap()
+    }
+
+    fn configurable(name: &str, offset: u64) -> FullConfigurable {
+        FullConfigurable {
+            name: name.to_string(),
+            application: application("u64"),
+            offset,
+            indirect: false,
+        }
+    }
+
+    fn abi(
+        functions: Vec<FullABIFunction>,
+        types: Vec<&str>,
+        configurables: Vec<FullConfigurable>,
+    ) -> FullProgramABI {
+        FullProgramABI {
+            types: types.into_iter().map(type_decl).collect(),
+            functions,
+            configurables,
+            ..full_abi()
+        }
+    }
+
+    #[test]
+    fn identical_abis_have_no_differences() {
+        let old = abi(
+            vec![function("a", "u64", "doc")],
+            vec!["u64"],
+            vec![configurable("C", 0)],
+        );
+
+        let diff = old.diff(&old.clone());
+
+        assert!(diff.added_functions.is_empty());
+        assert!(diff.removed_functions.is_empty());
+        assert!(diff.changed_functions.is_empty());
+        assert!(diff.added_types.is_empty());
+        assert!(diff.removed_types.is_empty());
+        assert!(diff.changed_configurables.is_empty());
+        assert!(diff.is_empty());
+        assert_eq!(diff.to_string(), "no changes\n");
+    }
+
+    #[test]
+    fn reports_every_kind_of_change() {
+        let old = abi(
+            vec![
+                function("removed", "u64", "doc"),
+                function("signature", "u64", "doc"),
+                function("docs", "u64", "doc"),
+            ],
+            vec!["u64", "u8"],
+            vec![configurable("MOVED", 0), configurable("REMOVED", 8)],
+        );
+        let new = abi(
+            vec![
+                function("signature", "u8", "doc"),
+                function("docs", "u64", "new doc"),
+                function("added", "u64", "doc"),
+            ],
+            vec!["u64", "bool"],
+            vec![configurable("MOVED", 8), configurable("ADDED", 16)],
+        );
+
+        let diff = old.diff(&new);
+
+        assert_eq!(diff.added_functions, ["added"]);
+        assert_eq!(diff.removed_functions, ["removed"]);
+        let changes: Vec<_> = diff
+            .changed_functions
+            .iter()
+            .map(|change| (change.name.as_str(), change.kind))
+            .collect();
+        assert_eq!(
+            changes,
+            [
+                ("signature", FunctionChangeKind::Signature),
+                ("docs", FunctionChangeKind::Attributes)
+            ]
+        );
+        assert_eq!(diff.added_types, [type_decl("bool")]);
+        assert_eq!(diff.removed_types, [type_decl("u8")]);
+        assert_eq!(diff.added_configurables, ["ADDED"]);
+        assert_eq!(diff.removed_configurables, ["REMOVED"]);
+        assert_eq!(diff.changed_configurables, ["MOVED"]);
+        assert!(!diff.is_empty());
+        assert_eq!(
+            diff.to_string(),
+            "+ fn added\n\
+             - fn removed\n\
+             ~ fn signature (signature)\n\
+             ~ fn docs (attributes)\n\
+             + type bool\n\
+             - type u8\n\
+             + configurable ADDED\n\
+             - configurable REMOVED\n\
+             ~ configurable MOVED\n"
+        );
+
+        let json = serde_json::to_string(&diff).unwrap();
+        assert_eq!(serde_json::from_str::<AbiDiff>(&json).unwrap(), diff);
+    }
+
+    #[test]
+    fn moved_configurables_are_reported() {
+        let old = abi(
+            vec![],
+            vec![],
+            vec![configurable("A", 0), configurable("B", 8)],
+        );
+        let new = abi(
+            vec![],
+            vec![],
+            vec![configurable("A", 0), configurable("B", 16)],
+        );
+
+        assert_eq!(
+            old.configurable_diff(&new),
+            [ConfigurableChange::Offset {
+                name: "B".to_string(),
+                old: 8,
+                new: 16
+            }]
+        );
+        assert!(old.configurable_diff(&old).is_empty());
+    }
+
+    #[test]
+    fn configurable_type_changes_additions_and_removals_are_reported() {
+        let mut retyped = configurable("RETYPED", 0);
+        retyped.application = application("u8");
+        let old = abi(
+            vec![],
+            vec![],
+            vec![configurable("RETYPED", 0), configurable("REMOVED", 8)],
+        );
+        let new = abi(vec![], vec![], vec![retyped, configurable("ADDED", 8)]);
+
+        assert_eq!(
+            old.configurable_diff(&new),
+            [
+                ConfigurableChange::Removed("REMOVED".to_string()),
+                ConfigurableChange::Type {
+                    name: "RETYPED".to_string(),
+                    old: Box::new(application("u64")),
+                    new: Box::new(application("u8")),
+                },
+                ConfigurableChange::Added("ADDED".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn configurables_becoming_indirect_are_reported() {
+        let mut indirect = configurable("A", 0);
+        indirect.indirect = true;
+        let old = abi(vec![], vec![], vec![configurable("A", 0)]);
+        let new = abi(vec![], vec![], vec![indirect]);
+
+        assert_eq!(
+            old.configurable_diff(&new),
+            [ConfigurableChange::Indirect {
+                name: "A".to_string(),
+                old: false,
+                new: true,
+            }]
+        );
+    }
+
+    #[test]
+    fn renaming_a_parameter_is_not_a_signature_change() {
+        let taking = |parameter: &str| {
+            FullABIFunction::new(
+                "a".to_string(),
+                vec![appl(parameter, type_decl("u64"))],
+                application("()"),
+                vec![],
+            )
+            .unwrap()
+        };
+        let old = abi(vec![taking("value")], vec![], vec![]);
+        let new = abi(vec![taking("renamed")], vec![], vec![]);
+
+        let diff = old.diff(&new);
+
+        assert_eq!(
+            diff.changed_functions,
+            [FunctionChange {
+                name: "a".to_string(),
+                kind: FunctionChangeKind::Attributes
+            }]
+        );
+        assert!(diff.breaking_changes().is_empty());
+    }
+
+    #[test]
+    fn only_removals_and_signature_changes_are_breaking() {
+        let old = abi(
+            vec![
+                function("renamed", "u64", "doc"),
+                function("signature", "u64", "doc"),
+                function("docs", "u64", "doc"),
+            ],
+            vec![],
+            vec![],
+        );
+        let new = abi(
+            vec![
+                function("signature", "u8", "doc"),
+                function("docs", "u64", "new doc"),
+                function("was_renamed", "u64", "doc"),
+            ],
+            vec![],
+            vec![],
+        );
+
+        let breaking_changes = old.diff(&new).breaking_changes();
+
+        assert_eq!(
+            breaking_changes,
+            [
+                BreakingChange::RemovedFunction("renamed".to_string()),
+                BreakingChange::ChangedSignature("signature".to_string())
+            ]
+        );
+        assert!(new.diff(&new).breaking_changes().is_empty());
+    }
+}
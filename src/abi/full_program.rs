@@ -1,6 +1,14 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
-use crate::{abi::program::Attribute, utils::extract_custom_type_name};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    abi::program::{Attribute, InlineKind},
+    utils::{
+        extract_custom_type_name, extract_generic_name, has_tuple_format,
+        is_synthetic_component_name, try_extract_array_len, try_extract_str_len,
+    },
+};
 
 use crate::{
     error::{error, Error, Result},
@@ -8,10 +16,14 @@ use crate::{
 };
 
 use super::{
-    program::Version,
+    program::{
+        ABIFunction, ConcreteTypeId, Configurable, LogId, LoggedType, MessageId, MessageType,
+        MetadataTypeId, ProgramABI, ProgramType, StorageSlot, TypeApplication,
+        TypeConcreteDeclaration, TypeConcreteParameter, TypeId, TypeMetadataDeclaration, Version,
+    },
     unified_program::{
-        UnifiedABIFunction, UnifiedConfigurable, UnifiedLoggedType, UnifiedProgramABI,
-        UnifiedTypeApplication, UnifiedTypeDeclaration,
+        UnifiedABIFunction, UnifiedConfigurable, UnifiedProgramABI, UnifiedTypeApplication,
+        UnifiedTypeDeclaration,
     },
 };
 
@@ -20,7 +32,10 @@ use super::{
 /// [`UnifiedTypeDeclaration`] and others) are not suited for this due to their use of
 /// ids, which might differ between contracts even though the type they
 /// represent is virtually the same.
-#[derive(Debug, Clone)]
+///
+/// Being an owned tree, the 'Full' representation cannot contain cycles, so it can be
+/// safely (de)serialized to cache the result of the unification.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FullProgramABI {
     pub program_type: String,
     pub spec_version: Version,
@@ -29,6 +44,10 @@ pub struct FullProgramABI {
     pub functions: Vec<FullABIFunction>,
     pub logged_types: Vec<FullLoggedType>,
     pub configurables: Vec<FullConfigurable>,
+    pub messages_types: Vec<FullMessageType>,
+    /// Empty when the ABI has no storage section.
+    #[serde(default)]
+    pub storage_slots: Vec<FullStorageSlot>,
 }
 
 impl FullProgramABI {
@@ -37,38 +56,184 @@ impl FullProgramABI {
         FullProgramABI::from_counterpart(&unified_program_abi)
     }
 
-    fn from_counterpart(unified_program_abi: &UnifiedProgramABI) -> Result<FullProgramABI> {
-        let lookup: HashMap<_, _> = unified_program_abi
-            .types
+    /// Serializes the ABI back into the JSON format emitted by the compiler. See
+    /// [`FullProgramABI::to_program_abi`] for how the type declarations are rebuilt.
+    pub fn to_json_abi(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(&self.to_program_abi())?)
+    }
+
+    /// Rebuilds the concrete and metadata type declarations of the ABI.
+    ///
+    /// Every entry of `types` becomes a metadata type, with the position of the entry as
+    /// its id. Concrete type ids are derived from the order in which the applications are
+    /// first encountered, they are valid ids but won't match the ones the compiler
+    /// assigns.
+    pub fn to_program_abi(&self) -> ProgramABI {
+        let mut builder = ProgramAbiBuilder::default();
+        for type_decl in &self.types {
+            builder.declare_metadata_type(type_decl, false);
+        }
+
+        let functions = self
+            .functions
             .iter()
-            .map(|ttype| (ttype.type_id, ttype.clone()))
+            .map(|function| ABIFunction {
+                inputs: function
+                    .inputs
+                    .iter()
+                    .map(|input| TypeConcreteParameter {
+                        name: input.name.clone(),
+                        concrete_type_id: builder.concrete_type_id(input),
+                    })
+                    .collect(),
+                name: function.name.clone(),
+                output: builder.concrete_type_id(&function.output),
+                attributes: (!function.attributes.is_empty()).then(|| function.attributes.clone()),
+            })
             .collect();
 
-        let types = unified_program_abi
+        let logged_types = self
+            .logged_types
+            .iter()
+            .map(|logged_type| LoggedType {
+                log_id: logged_type.log_id.to_string(),
+                concrete_type_id: builder.concrete_type_id(&logged_type.application),
+            })
+            .collect::<Vec<_>>();
+
+        let messages_types = self
+            .messages_types
+            .iter()
+            .map(|message_type| MessageType {
+                message_id: message_type.message_id.to_string(),
+                concrete_type_id: builder.concrete_type_id(&message_type.application),
+            })
+            .collect::<Vec<_>>();
+
+        let configurables = self
+            .configurables
+            .iter()
+            .map(|configurable| Configurable {
+                name: configurable.name.clone(),
+                concrete_type_id: builder.concrete_type_id(&configurable.application),
+                offset: configurable.offset,
+                indirect: configurable.indirect,
+            })
+            .collect::<Vec<_>>();
+
+        let storage_slots = self
+            .storage_slots
+            .iter()
+            .map(|storage_slot| StorageSlot {
+                key: storage_slot.key.clone(),
+                value: storage_slot.value.clone(),
+                concrete_type_id: storage_slot
+                    .application
+                    .as_ref()
+                    .map(|application| builder.concrete_type_id(application)),
+            })
+            .collect::<Vec<_>>();
+
+        let (concrete_types, metadata_types) = builder.finish();
+
+        ProgramABI {
+            program_type: self.program_type.clone(),
+            spec_version: self.spec_version.clone(),
+            encoding_version: self.encoding_version.clone(),
+            concrete_types,
+            metadata_types,
+            functions,
+            logged_types: (!logged_types.is_empty()).then_some(logged_types),
+            messages_types: (!messages_types.is_empty()).then_some(messages_types),
+            configurables: (!configurables.is_empty()).then_some(configurables),
+            storage_slots: (!storage_slots.is_empty()).then_some(storage_slots),
+        }
+    }
+
+    fn from_counterpart(unified_program_abi: &UnifiedProgramABI) -> Result<FullProgramABI> {
+        // The lookup borrows the declarations instead of cloning them, and a missing type
+        // is reported rather than panicking.
+        let abi = unified_program_abi;
+        let lookup = abi.type_lookup();
+        let full_application =
+            |application| full_type_application(application, &lookup, &mut vec![]);
+
+        let types = abi
             .types
             .iter()
-            .map(|ttype| FullTypeDeclaration::from_counterpart(ttype, &lookup))
-            .collect();
+            .map(|type_decl| full_type_declaration(type_decl, &lookup, &mut vec![]))
+            .collect::<Result<Vec<_>>>()?;
 
-        let functions = unified_program_abi
+        let functions = abi
             .functions
             .iter()
-            .map(|fun| FullABIFunction::from_counterpart(fun, &lookup))
+            .map(|fun| {
+                FullABIFunction::new(
+                    fun.name.clone(),
+                    fun.inputs
+                        .iter()
+                        .map(full_application)
+                        .collect::<Result<_>>()?,
+                    full_application(&fun.output)?,
+                    fun.attributes.clone().unwrap_or_default(),
+                )
+            })
             .collect::<Result<Vec<_>>>()?;
 
-        let logged_types = unified_program_abi
+        let logged_types = abi
             .logged_types
             .iter()
             .flatten()
-            .map(|logged_type| FullLoggedType::from_counterpart(logged_type, &lookup))
-            .collect();
+            .map(|logged_type| {
+                Ok(FullLoggedType {
+                    log_id: logged_type.log_id,
+                    application: full_application(&logged_type.application)?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
 
-        let configurables = unified_program_abi
+        let configurables = abi
             .configurables
             .iter()
             .flatten()
-            .map(|configurable| FullConfigurable::from_counterpart(configurable, &lookup))
-            .collect();
+            .map(|configurable| {
+                Ok(FullConfigurable {
+                    name: configurable.name.clone(),
+                    application: full_application(&configurable.application)?,
+                    offset: configurable.offset,
+                    indirect: configurable.indirect,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let messages_types = abi
+            .messages_types
+            .iter()
+            .flatten()
+            .map(|message_type| {
+                Ok(FullMessageType {
+                    message_id: message_type.message_id,
+                    application: full_application(&message_type.application)?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let storage_slots = abi
+            .storage_slots
+            .iter()
+            .flatten()
+            .map(|storage_slot| {
+                Ok(FullStorageSlot {
+                    key: storage_slot.key.clone(),
+                    value: storage_slot.value.clone(),
+                    application: storage_slot
+                        .application
+                        .as_ref()
+                        .map(full_application)
+                        .transpose()?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
 
         Ok(Self {
             program_type: unified_program_abi.program_type.clone(),
@@ -78,11 +243,385 @@ impl FullProgramABI {
             functions,
             logged_types,
             configurables,
+            messages_types,
+            storage_slots,
         })
     }
+
+    pub fn program_kind(&self) -> ProgramType {
+        ProgramType::from(self.program_type.clone())
+    }
+
+    /// Returns the functions taking or returning a value that is, or contains, a heap
+    /// type.
+    pub fn functions_with_heap_types(&self) -> Vec<&FullABIFunction> {
+        self.functions
+            .iter()
+            .filter(|function| {
+                function
+                    .inputs
+                    .iter()
+                    .chain([&function.output])
+                    .any(FullTypeApplication::contains_heap_types)
+            })
+            .collect()
+    }
+
+    /// Returns each distinct list of type arguments the generic type at `path` is
+    /// instantiated with, wherever it appears in the ABI, including nested in other
+    /// types. Lists are returned in the order they are first encountered.
+    pub fn instantiations_of(&self, path: &TypePath) -> Vec<Vec<FullTypeApplication>> {
+        fn collect(
+            application: &FullTypeApplication,
+            path: &TypePath,
+            instantiations: &mut Vec<Vec<FullTypeApplication>>,
+        ) {
+            let type_decl = &application.type_decl;
+            if !application.type_arguments.is_empty()
+                && type_decl.is_custom_type()
+                && type_decl.custom_type_path().is_ok_and(|p| &p == path)
+            {
+                let type_arguments: Vec<_> = application
+                    .type_arguments
+                    .iter()
+                    .map(|argument| FullTypeApplication {
+                        name: "".to_string(),
+                        ..argument.clone()
+                    })
+                    .collect();
+                if !instantiations.contains(&type_arguments) {
+                    instantiations.push(type_arguments);
+                }
+            }
+
+            for nested in application
+                .type_arguments
+                .iter()
+                .chain(&type_decl.components)
+            {
+                collect(nested, path, instantiations);
+            }
+        }
+
+        let applications = self
+            .functions
+            .iter()
+            .flat_map(|function| function.inputs.iter().chain([&function.output]))
+            .chain(self.logged_types.iter().map(|logged| &logged.application))
+            .chain(
+                self.messages_types
+                    .iter()
+                    .map(|message| &message.application),
+            )
+            .chain(
+                self.configurables
+                    .iter()
+                    .map(|configurable| &configurable.application),
+            )
+            .chain(self.storage_slots.iter().flat_map(|slot| &slot.application));
+
+        let mut instantiations = vec![];
+        for application in applications {
+            collect(&application.monomorphized(), path, &mut instantiations);
+        }
+
+        instantiations
+    }
+
+    /// Collects the doc comments of the ABI. Undocumented functions are left out.
+    pub fn all_doc_comments(&self) -> Result<DocIndex> {
+        let mut functions = BTreeMap::new();
+        for function in &self.functions {
+            let doc_strings = function.doc_strings()?;
+            if !doc_strings.is_empty() {
+                functions.insert(function.name.clone(), doc_strings);
+            }
+        }
+
+        Ok(DocIndex { functions })
+    }
+
+    pub fn iter_functions(&self) -> impl Iterator<Item = &FullABIFunction> {
+        self.functions.iter()
+    }
+
+    /// Returns the function called `name`, if any.
+    pub fn get_function(&self, name: &str) -> Option<&FullABIFunction> {
+        self.functions.iter().find(|function| function.name == name)
+    }
+
+    pub fn is_script(&self) -> bool {
+        self.program_kind() == ProgramType::Script
+    }
+
+    pub fn is_contract(&self) -> bool {
+        self.program_kind() == ProgramType::Contract
+    }
+
+    /// Whether the ABI describes a predicate. Predicates are entered through `main`
+    /// and their configurables are set when the predicate's bytecode is built for
+    /// spending, which changes the predicate's address.
+    pub fn is_predicate(&self) -> bool {
+        self.program_kind() == ProgramType::Predicate
+    }
+
+    pub fn is_library(&self) -> bool {
+        self.program_kind() == ProgramType::Library
+    }
+
+    /// Returns the functions that can be called from outside of the program. Every
+    /// function of a contract is an entry point, while scripts and predicates are only
+    /// entered through `main`. Libraries have no entry points. All functions are
+    /// returned for unknown program types.
+    pub fn entry_points(&self) -> Vec<&FullABIFunction> {
+        match self.program_kind() {
+            ProgramType::Script | ProgramType::Predicate => self
+                .functions
+                .iter()
+                .filter(|function| function.name == "main")
+                .collect(),
+            ProgramType::Library => vec![],
+            ProgramType::Contract | ProgramType::Other(_) => self.functions.iter().collect(),
+        }
+    }
+
+    /// Returns a copy of the ABI with all function attributes removed. Useful for
+    /// comparing the callable surface of two ABIs while ignoring changes to doc
+    /// comments and other attributes.
+    pub fn without_attributes(&self) -> FullProgramABI {
+        let mut abi = self.clone();
+        for function in &mut abi.functions {
+            function.attributes.clear();
+        }
+
+        abi
+    }
+
+    /// Returns a copy of the ABI with the offsets of all configurables set to 0. Two
+    /// builds of the same program may only differ in where their configurables ended
+    /// up in the binary.
+    pub fn without_configurable_offsets(&self) -> FullProgramABI {
+        let mut abi = self.clone();
+        for configurable in &mut abi.configurables {
+            configurable.offset = 0;
+        }
+
+        abi
+    }
+
+    /// Compares two ABIs, ignoring the offsets of their configurables.
+    pub fn eq_ignoring_configurable_offsets(&self, other: &FullProgramABI) -> bool {
+        self.without_configurable_offsets() == other.without_configurable_offsets()
+    }
+
+    /// Checks that every configurable fits within a binary of `binary_len` bytes.
+    ///
+    /// Indirect configurables only store a word-sized pointer at their offset. For the
+    /// rest, the encoded size of the configurable's type must fit as well. If the size
+    /// isn't statically known, the offset must point within the binary.
+    pub fn validate_configurable_offsets(&self, binary_len: usize) -> Result<()> {
+        for configurable in &self.configurables {
+            let size = if configurable.indirect {
+                Some(WORD_SIZE)
+            } else {
+                configurable.application.encoded_size()
+            };
+
+            let end = usize::try_from(configurable.offset)
+                .ok()
+                .and_then(|offset| offset.checked_add(size.unwrap_or(1)));
+
+            if !matches!(end, Some(end) if end <= binary_len) {
+                return Err(match size {
+                    Some(size) => error!(
+                        "configurable '{}' at offset {} with size {size} does not fit into a binary of {binary_len} bytes",
+                        configurable.name,
+                        configurable.offset
+                    ),
+                    None => error!(
+                        "configurable '{}' at offset {} is beyond the end of a binary of {binary_len} bytes",
+                        configurable.name,
+                        configurable.offset
+                    ),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+const WORD_SIZE: usize = 8;
+
+/// The std types known by their short name, see [`FullTypeDeclaration::std_short_name`].
+const STD_SHORT_NAMES: &[(&str, &str)] = &[
+    ("std::address::Address", "Address"),
+    ("std::asset_id::AssetId", "AssetId"),
+    ("std::b512::B512", "B512"),
+    ("std::bytes::Bytes", "Bytes"),
+    ("std::contract_id::ContractId", "ContractId"),
+    ("std::identity::Identity", "Identity"),
+    ("std::option::Option", "Option"),
+    ("std::result::Result", "Result"),
+    ("std::string::String", "String"),
+    ("std::vec::Vec", "Vec"),
+];
+
+fn primitive_encoded_size(type_field: &str) -> Option<usize> {
+    let size = match type_field {
+        "()" => 0,
+        "bool" | "u8" => 1,
+        "u16" => 2,
+        "u32" => 4,
+        "u64" | "raw untyped ptr" => WORD_SIZE,
+        "u256" | "b256" => 32,
+        _ => return None,
+    };
+
+    Some(size)
+}
+
+/// Compact binary form of the ABI, e.g. for caching the result of the unification
+/// across builds. Being an owned tree, the decoded ABI cannot contain cycles.
+#[cfg(feature = "bincode")]
+impl FullProgramABI {
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        use bincode::Options;
+
+        Ok(bincode::DefaultOptions::new().serialize(self)?)
+    }
+
+    /// Decodes an ABI produced by [`FullProgramABI::to_bytes`]. Length prefixes larger
+    /// than the input are rejected instead of being allocated.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        use bincode::Options;
+
+        Ok(bincode::DefaultOptions::new()
+            .with_limit(bytes.len() as u64)
+            .deserialize(bytes)?)
+    }
+}
+
+impl TryFrom<&UnifiedProgramABI> for FullProgramABI {
+    type Error = Error;
+
+    fn try_from(unified_program_abi: &UnifiedProgramABI) -> Result<Self> {
+        FullProgramABI::from_counterpart(unified_program_abi)
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// Assigns ids to the type declarations and applications of a [`FullProgramABI`] while
+/// it is being turned back into a [`ProgramABI`].
+#[derive(Default)]
+struct ProgramAbiBuilder {
+    metadata_ids: HashMap<FullTypeDeclaration, MetadataTypeId>,
+    /// The declarations behind `metadata_types`, whose components are filled in by
+    /// [`ProgramAbiBuilder::finish`].
+    metadata_decls: Vec<FullTypeDeclaration>,
+    metadata_types: Vec<TypeMetadataDeclaration>,
+    concrete_ids: HashMap<FullTypeApplication, ConcreteTypeId>,
+    concrete_types: Vec<TypeConcreteDeclaration>,
+}
+
+impl ProgramAbiBuilder {
+    /// Adds a metadata type for `type_decl`. Unless `reuse` is set, a new one is added
+    /// even if an identical declaration was seen before.
+    fn declare_metadata_type(
+        &mut self,
+        type_decl: &FullTypeDeclaration,
+        reuse: bool,
+    ) -> MetadataTypeId {
+        if reuse {
+            if let Some(id) = self.metadata_ids.get(type_decl) {
+                return id.clone();
+            }
+        }
+
+        let id = MetadataTypeId(self.metadata_types.len());
+        self.metadata_ids
+            .entry(type_decl.clone())
+            .or_insert_with(|| id.clone());
+        self.metadata_decls.push(type_decl.clone());
+        self.metadata_types.push(TypeMetadataDeclaration {
+            type_field: type_decl.type_field.clone(),
+            metadata_type_id: id.clone(),
+            components: None,
+            type_parameters: None,
+        });
+
+        id
+    }
+
+    fn type_application(&mut self, application: &FullTypeApplication) -> TypeApplication {
+        let type_arguments = application
+            .type_arguments
+            .iter()
+            .map(|argument| self.type_application(argument))
+            .collect::<Vec<_>>();
+
+        TypeApplication {
+            name: application.name.clone(),
+            type_id: TypeId::Metadata(self.declare_metadata_type(&application.type_decl, true)),
+            type_arguments: (!type_arguments.is_empty()).then_some(type_arguments),
+        }
+    }
+
+    fn concrete_type_id(&mut self, application: &FullTypeApplication) -> ConcreteTypeId {
+        let key = FullTypeApplication {
+            name: "".to_string(),
+            ..application.clone()
+        };
+        if let Some(id) = self.concrete_ids.get(&key) {
+            return id.clone();
+        }
+
+        let type_arguments = application
+            .type_arguments
+            .iter()
+            .map(|argument| self.concrete_type_id(argument))
+            .collect::<Vec<_>>();
+        let metadata_type_id = self.declare_metadata_type(&application.type_decl, true);
+        let id = ConcreteTypeId(format!("{:064x}", self.concrete_types.len()));
+        self.concrete_types.push(TypeConcreteDeclaration {
+            type_field: application.type_decl.type_field.clone(),
+            concrete_type_id: id.clone(),
+            metadata_type_id: Some(metadata_type_id),
+            type_arguments: (!type_arguments.is_empty()).then_some(type_arguments),
+        });
+        self.concrete_ids.insert(key, id.clone());
+
+        id
+    }
+
+    fn finish(mut self) -> (Vec<TypeConcreteDeclaration>, Vec<TypeMetadataDeclaration>) {
+        // Filling in components may declare further metadata types.
+        let mut position = 0;
+        while position < self.metadata_decls.len() {
+            let type_decl = self.metadata_decls[position].clone();
+            let components = type_decl
+                .components
+                .iter()
+                .map(|component| self.type_application(component))
+                .collect::<Vec<_>>();
+            let type_parameters = type_decl
+                .type_parameters
+                .iter()
+                .map(|type_parameter| self.declare_metadata_type(type_parameter, true))
+                .collect::<Vec<_>>();
+
+            let metadata_type = &mut self.metadata_types[position];
+            metadata_type.components = (!components.is_empty()).then_some(components);
+            metadata_type.type_parameters =
+                (!type_parameters.is_empty()).then_some(type_parameters);
+            position += 1;
+        }
+
+        (self.concrete_types, self.metadata_types)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "RawFullABIFunction")]
 pub struct FullABIFunction {
     name: String,
     inputs: Vec<FullTypeApplication>,
@@ -90,6 +629,24 @@ pub struct FullABIFunction {
     attributes: Vec<Attribute>,
 }
 
+/// Mirrors [`FullABIFunction`] so that deserialization goes through
+/// [`FullABIFunction::new`] and its validation.
+#[derive(Deserialize)]
+struct RawFullABIFunction {
+    name: String,
+    inputs: Vec<FullTypeApplication>,
+    output: FullTypeApplication,
+    attributes: Vec<Attribute>,
+}
+
+impl TryFrom<RawFullABIFunction> for FullABIFunction {
+    type Error = Error;
+
+    fn try_from(raw: RawFullABIFunction) -> Result<Self> {
+        Self::new(raw.name, raw.inputs, raw.output, raw.attributes)
+    }
+}
+
 impl FullABIFunction {
     pub fn new(
         name: String,
@@ -125,20 +682,64 @@ impl FullABIFunction {
         self.attributes.iter().any(|attr| attr.name == "payable")
     }
 
+    /// Bundles the attributes callers consult before building a transaction calling
+    /// the function.
+    pub fn call_requirements(&self) -> CallRequirements {
+        let storage_access = |access: &str| {
+            self.attributes
+                .iter()
+                .filter(|attr| attr.name == "storage")
+                .flat_map(|attr| &attr.arguments)
+                .any(|arg| arg == access)
+        };
+
+        CallRequirements {
+            payable: self.is_payable(),
+            reads_storage: storage_access("read"),
+            writes_storage: storage_access("write"),
+        }
+    }
+
+    pub fn is_test(&self) -> bool {
+        self.attributes.iter().any(|attr| attr.name == "test")
+    }
+
+    /// Returns the argument of the function's `inline` attribute, if it has a
+    /// well-formed one.
+    pub fn inline_hint(&self) -> Option<InlineKind> {
+        self.attributes
+            .iter()
+            .find(|attr| attr.name == "inline")?
+            .as_inline()
+    }
+
+    /// Whether a call to the function can complete without reverting, i.e. whether its
+    /// output type has any value. Decoding the output of other functions makes no sense.
+    pub fn can_return_normally(&self) -> bool {
+        !self.output.type_decl.is_never_returning()
+    }
+
+    /// Whether the function returns the unit type `()`, i.e. has no return value.
+    pub fn returns_unit(&self) -> bool {
+        self.output.type_decl.is_unit_type()
+    }
+
     pub fn doc_strings(&self) -> Result<Vec<String>> {
         self.attributes
             .iter()
             .filter(|attr| attr.name == "doc-comment")
             .map(|attr| {
                 (attr.arguments.len() == 1)
-                    .then_some(attr.arguments[0].clone())
-                    .ok_or_else(|| {
-                        Error("`doc-comment` attribute must have one argument".to_string())
-                    })
+                    .then(|| attr.arguments[0].clone())
+                    .ok_or_else(|| error!("`doc-comment` attribute must have one argument"))
             })
             .collect::<Result<Vec<String>>>()
     }
 
+    #[deprecated(
+        note = "panics on undeclared types, convert through `FullProgramABI::try_from` instead"
+    )]
+    #[allow(deprecated)]
     pub fn from_counterpart(
         abi_function: &UnifiedABIFunction,
         types: &HashMap<usize, UnifiedTypeDeclaration>,
@@ -162,7 +763,7 @@ impl FullABIFunction {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct FullTypeDeclaration {
     pub type_field: String,
     pub components: Vec<FullTypeApplication>,
@@ -170,6 +771,10 @@ pub struct FullTypeDeclaration {
 }
 
 impl FullTypeDeclaration {
+    #[deprecated(
+        note = "panics on undeclared types, convert through `FullTypeDeclarationRef::to_full` instead"
+    )]
+    #[allow(deprecated)]
     pub fn from_counterpart(
         type_decl: &UnifiedTypeDeclaration,
         types: &HashMap<usize, UnifiedTypeDeclaration>,
@@ -202,9 +807,203 @@ impl FullTypeDeclaration {
 
         TypePath::new(type_name)
     }
+
+    /// Returns the size of this type when encoded following the ABI encoding spec
+    /// (encoding version 1), if it is statically known.
+    ///
+    /// `None` is returned for heap types, string slices, generics and enums whose
+    /// variants differ in size, since their encoded length depends on the value.
+    /// Use [`FullTypeApplication::encoded_size`] to have generics replaced by their
+    /// type arguments first.
+    ///
+    /// Encoding version 1 writes an enum as its `u64` tag followed by the active
+    /// variant, without padding the variant to the size of the largest one. An enum
+    /// therefore only has a static size, the tag plus the variant size, when all its
+    /// variants are the same size. `Option<u64>` for instance takes 8 bytes when `None`
+    /// and 16 when `Some`.
+    pub fn encoded_size(&self) -> Option<usize> {
+        if self.is_heap_type() {
+            return None;
+        }
+
+        if let Some(size) = primitive_encoded_size(&self.type_field) {
+            return Some(size);
+        }
+
+        if self.is_b512() {
+            return Some(64);
+        }
+
+        if let Some(len) = try_extract_str_len(&self.type_field).ok()? {
+            return Some(len);
+        }
+
+        if let Some(len) = try_extract_array_len(&self.type_field).ok()? {
+            return self.components.first()?.encoded_size()?.checked_mul(len);
+        }
+
+        if has_tuple_format(&self.type_field) || self.is_struct_type() {
+            return self.components.iter().try_fold(0usize, |size, component| {
+                size.checked_add(component.encoded_size()?)
+            });
+        }
+
+        if self.is_enum_type() {
+            let (first, rest) = self.components.split_first()?;
+            let variant_size = first.encoded_size()?;
+            let all_same_size = rest
+                .iter()
+                .all(|variant| variant.encoded_size() == Some(variant_size));
+
+            return all_same_size
+                .then(|| WORD_SIZE.checked_add(variant_size))
+                .flatten();
+        }
+
+        None
+    }
+
+    /// Renders the type on a single line, with its components nested up to `max_depth`
+    /// levels deep and elided as `...` beyond that. Meant for logs, where the derived
+    /// `Debug` output of deeply nested types is too large to be useful.
+    ///
+    /// For example `struct Foo { a: u8, b: struct Bar { ... } }` for `max_depth` 1.
+    pub fn debug_summary(&self, max_depth: usize) -> String {
+        let mut summary = String::new();
+        self.write_debug_summary(&[], max_depth, &mut summary);
+
+        summary
+    }
+
+    fn write_debug_summary(
+        &self,
+        type_arguments: &[FullTypeApplication],
+        depth_left: usize,
+        summary: &mut String,
+    ) {
+        summary.push_str(&self.type_field);
+
+        if !type_arguments.is_empty() {
+            summary.push('<');
+            if depth_left == 0 {
+                summary.push_str("...");
+            } else {
+                for (position, argument) in type_arguments.iter().enumerate() {
+                    if position > 0 {
+                        summary.push_str(", ");
+                    }
+                    argument.type_decl.write_debug_summary(
+                        &argument.type_arguments,
+                        depth_left - 1,
+                        summary,
+                    );
+                }
+            }
+            summary.push('>');
+        }
+
+        if self.components.is_empty() {
+            return;
+        }
+
+        if depth_left == 0 {
+            summary.push_str(" { ... }");
+            return;
+        }
+
+        summary.push_str(" { ");
+        for (position, component) in self.components.iter().enumerate() {
+            if position > 0 {
+                summary.push_str(", ");
+            }
+            summary.push_str(&component.name);
+            summary.push_str(": ");
+            component.type_decl.write_debug_summary(
+                &component.type_arguments,
+                depth_left - 1,
+                summary,
+            );
+        }
+        summary.push_str(" }");
+    }
+
+    /// Whether no value of this type can exist, as is the case of enums without variants
+    /// and of the never type `!`. A function returning such a type can only revert.
+    pub fn is_never_returning(&self) -> bool {
+        self.type_field == "!" || (self.is_enum_type() && self.components.is_empty())
+    }
+
+    /// Whether values of this type encode to no bytes at all, e.g. `()`, structs without
+    /// fields or zero length arrays. Enums are never zero-sized, as their tag is encoded
+    /// even when none of their variants carries data.
+    pub fn is_zero_sized(&self) -> bool {
+        self.encoded_size() == Some(0)
+    }
+
+    /// Returns a stable key describing the type, whatever its kind. Custom types are
+    /// described by their path, e.g. `std::vec::Vec`, tuples and arrays by their
+    /// elements, e.g. `(u8, u64)` and `[b256; 3]`, and other types by their type field.
+    pub fn canonical_path(&self) -> String {
+        let type_field = self.type_field.trim();
+
+        if self.is_custom_type() {
+            if let Ok(path) = self.custom_type_path() {
+                return path.to_string();
+            }
+        }
+
+        if let (Ok(Some(len)), [element]) = (
+            try_extract_array_len(type_field),
+            self.components.as_slice(),
+        ) {
+            return format!("[{}; {len}]", element.type_decl.canonical_path());
+        }
+
+        if has_tuple_format(type_field) && !self.components.is_empty() {
+            let elements = self
+                .components
+                .iter()
+                .map(|component| component.type_decl.canonical_path());
+            return format!("({})", itertools::join(elements, ", "));
+        }
+
+        type_field.to_string()
+    }
+
+    /// Returns the name renderers show for the well-known std types, e.g. `B512` for
+    /// `std::b512::B512`. Other types have no short name.
+    pub fn std_short_name(&self) -> Option<&'static str> {
+        if !self.is_custom_type() {
+            return None;
+        }
+
+        let path = self.custom_type_path().ok()?.to_string();
+        STD_SHORT_NAMES
+            .iter()
+            .find(|(std_path, _)| *std_path == path)
+            .map(|(_, short_name)| *short_name)
+    }
+
+    /// Whether this is `std::b512::B512`, which wraps two `b256`s and is commonly used
+    /// for signatures and public keys.
+    pub fn is_b512(&self) -> bool {
+        self.is_struct_type() && self.std_short_name() == Some("B512")
+    }
+
+    /// Whether values of this type live on the heap: `str`, `raw untyped slice`, and the
+    /// std `Vec`, `Bytes` and `String`. Such types have no static encoded size.
+    pub fn is_heap_type(&self) -> bool {
+        matches!(self.type_field.as_str(), "str" | "raw untyped slice")
+            || extract_custom_type_name(&self.type_field).is_some_and(|name| {
+                matches!(
+                    name.as_str(),
+                    "std::vec::Vec" | "std::bytes::Bytes" | "std::string::String"
+                )
+            })
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct FullTypeApplication {
     pub name: String,
     pub type_decl: FullTypeDeclaration,
@@ -212,6 +1011,10 @@ pub struct FullTypeApplication {
 }
 
 impl FullTypeApplication {
+    #[deprecated(
+        note = "panics on undeclared types, convert through `FullTypeApplicationRef::to_full` instead"
+    )]
+    #[allow(deprecated)]
     pub fn from_counterpart(
         type_application: &UnifiedTypeApplication,
         types: &HashMap<usize, UnifiedTypeDeclaration>,
@@ -235,35 +1038,195 @@ impl FullTypeApplication {
             type_arguments,
         }
     }
-}
 
-#[derive(Debug, Clone)]
-pub struct FullLoggedType {
-    pub log_id: String,
-    pub application: FullTypeApplication,
-}
+    /// Same as [`FullTypeDeclaration::debug_summary`], with the type arguments of the
+    /// application rendered after the type, e.g. `enum std::option::Option<u64> { ... }`.
+    pub fn debug_summary(&self, max_depth: usize) -> String {
+        let mut summary = String::new();
+        self.type_decl
+            .write_debug_summary(&self.type_arguments, max_depth, &mut summary);
 
-impl FullLoggedType {
-    fn from_counterpart(
-        logged_type: &UnifiedLoggedType,
-        types: &HashMap<usize, UnifiedTypeDeclaration>,
-    ) -> FullLoggedType {
-        FullLoggedType {
-            log_id: logged_type.log_id.clone(),
-            application: FullTypeApplication::from_counterpart(&logged_type.application, types),
-        }
+        summary
     }
-}
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
-pub struct FullConfigurable {
-    pub name: String,
-    pub application: FullTypeApplication,
-    pub offset: u64,
-    pub indirect: bool,
-}
+    /// Returns a copy of this application in which every `generic T` found in the
+    /// components of its declaration is replaced by the type argument bound to it.
+    /// Generics that aren't bound by a type argument are left untouched.
+    pub fn monomorphized(&self) -> FullTypeApplication {
+        self.monomorphized_with(&HashMap::new())
+    }
+
+    fn monomorphized_with(&self, bindings: &HashMap<String, FullTypeApplication>) -> Self {
+        if let Some(name) = extract_generic_name(&self.type_decl.type_field) {
+            return match bindings.get(&name) {
+                Some(bound) => FullTypeApplication {
+                    name: self.name.clone(),
+                    ..bound.clone()
+                },
+                None => self.clone(),
+            };
+        }
+
+        let type_arguments: Vec<_> = self
+            .type_arguments
+            .iter()
+            .map(|arg| arg.monomorphized_with(bindings))
+            .collect();
+
+        let own_bindings = self
+            .type_decl
+            .type_parameters
+            .iter()
+            .filter_map(|param| extract_generic_name(&param.type_field))
+            .zip(type_arguments.iter().cloned())
+            .collect();
+
+        let components = self
+            .type_decl
+            .components
+            .iter()
+            .map(|component| component.monomorphized_with(&own_bindings))
+            .collect();
+
+        FullTypeApplication {
+            name: self.name.clone(),
+            type_decl: FullTypeDeclaration {
+                type_field: self.type_decl.type_field.clone(),
+                components,
+                type_parameters: self.type_decl.type_parameters.clone(),
+            },
+            type_arguments,
+        }
+    }
+
+    /// Returns the statically known encoded size of the applied type, see
+    /// [`FullTypeDeclaration::encoded_size`].
+    pub fn encoded_size(&self) -> Option<usize> {
+        self.monomorphized().type_decl.encoded_size()
+    }
+
+    /// Whether the applied type is a heap type or contains one, once its generics are
+    /// bound. See [`FullTypeDeclaration::is_heap_type`].
+    pub fn contains_heap_types(&self) -> bool {
+        fn contains_heap_types(type_decl: &FullTypeDeclaration) -> bool {
+            type_decl.is_heap_type()
+                || type_decl
+                    .components
+                    .iter()
+                    .any(|component| contains_heap_types(&component.type_decl))
+        }
+
+        contains_heap_types(&self.monomorphized().type_decl)
+    }
+
+    /// Returns the path of the applied type along with those of its generic arguments,
+    /// e.g. `std::vec::Vec` with `u8` for a `Vec<u8>`. Tuples and arrays are described
+    /// by their elements.
+    pub fn applied_type_path(&self) -> Result<AppliedTypePath> {
+        let type_decl = &self.type_decl;
+        let type_field = &type_decl.type_field;
+
+        if type_decl.is_custom_type() {
+            return Ok(AppliedTypePath::Custom {
+                path: type_decl.custom_type_path()?,
+                args: self
+                    .type_arguments
+                    .iter()
+                    .map(FullTypeApplication::applied_type_path)
+                    .collect::<Result<_>>()?,
+            });
+        }
+
+        if let Some(name) = extract_generic_name(type_field) {
+            return Ok(AppliedTypePath::Generic(name));
+        }
+
+        if let Some(len) = try_extract_array_len(type_field)? {
+            let element = type_decl
+                .components
+                .first()
+                .ok_or_else(|| error!("array '{type_field}' has no element type"))?;
+            return Ok(AppliedTypePath::Array {
+                element: Box::new(element.applied_type_path()?),
+                len,
+            });
+        }
+
+        if has_tuple_format(type_field) && !type_decl.is_unit_type() {
+            return Ok(AppliedTypePath::Tuple(
+                type_decl
+                    .components
+                    .iter()
+                    .map(FullTypeApplication::applied_type_path)
+                    .collect::<Result<_>>()?,
+            ));
+        }
+
+        Ok(AppliedTypePath::Primitive(type_field.clone()))
+    }
+
+    /// Whether this application, or any type nested inside of it, refers to a
+    /// `generic T` that isn't bound by a type argument in scope. Such applications
+    /// aren't fully monomorphized.
+    pub fn has_unbound_generics(&self) -> bool {
+        self.has_unbound_generics_in(&HashSet::new())
+    }
+
+    fn has_unbound_generics_in(&self, bound_generics: &HashSet<String>) -> bool {
+        if let Some(name) = extract_generic_name(&self.type_decl.type_field) {
+            return !bound_generics.contains(&name);
+        }
+
+        if self
+            .type_arguments
+            .iter()
+            .any(|arg| arg.has_unbound_generics_in(bound_generics))
+        {
+            return true;
+        }
+
+        let type_parameters = &self.type_decl.type_parameters;
+        if self.type_arguments.len() < type_parameters.len() {
+            return true;
+        }
+
+        let bound_by_arguments = type_parameters
+            .iter()
+            .filter_map(|param| extract_generic_name(&param.type_field))
+            .collect();
+
+        self.type_decl
+            .components
+            .iter()
+            .any(|component| component.has_unbound_generics_in(&bound_by_arguments))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FullLoggedType {
+    pub log_id: LogId,
+    pub application: FullTypeApplication,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FullMessageType {
+    pub message_id: MessageId,
+    pub application: FullTypeApplication,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct FullConfigurable {
+    pub name: String,
+    pub application: FullTypeApplication,
+    pub offset: u64,
+    pub indirect: bool,
+}
 
 impl FullConfigurable {
+    #[deprecated(
+        note = "panics on undeclared types, convert through `FullProgramABI::try_from` instead"
+    )]
+    #[allow(deprecated)]
     pub fn from_counterpart(
         configurable: &UnifiedConfigurable,
         types: &HashMap<usize, UnifiedTypeDeclaration>,
@@ -277,7 +1240,220 @@ impl FullConfigurable {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct FullStorageSlot {
+    pub key: String,
+    pub value: String,
+    pub application: Option<FullTypeApplication>,
+}
+
+/// The doc comments found in an ABI, see [`FullProgramABI::all_doc_comments`].
+///
+/// Only functions carry attributes in the ABI, so only their docs are indexed. The doc
+/// comments of types, fields and configurables aren't emitted by the compiler.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DocIndex {
+    /// The doc comment lines of each documented function, keyed by function name.
+    pub functions: BTreeMap<String, Vec<String>>,
+}
+
+/// What a call to a function requires, see [`FullABIFunction::call_requirements`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CallRequirements {
+    /// Whether the call may forward coins.
+    pub payable: bool,
+    pub reads_storage: bool,
+    pub writes_storage: bool,
+}
+
+impl CallRequirements {
+    /// Whether the function is `payable` and has `storage(read)` but not `storage(write)`
+    /// access.
+    pub fn is_payable_read_only(&self) -> bool {
+        self.payable && self.reads_storage && !self.writes_storage
+    }
+}
+
+/// A type path with its generic arguments applied, see
+/// [`FullTypeApplication::applied_type_path`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AppliedTypePath {
+    /// A struct or enum.
+    Custom {
+        path: TypePath,
+        args: Vec<AppliedTypePath>,
+    },
+    Tuple(Vec<AppliedTypePath>),
+    Array {
+        element: Box<AppliedTypePath>,
+        len: usize,
+    },
+    /// A type without a path, such as `u64`, `()` or `str[4]`, by its type field.
+    Primitive(String),
+    /// A generic parameter that isn't bound, by its name.
+    Generic(String),
+}
+
+/// Enums from the Sway std library that map onto their Rust counterparts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StdEnum {
+    Option,
+    Result,
+}
+
+/// Borrowed counterpart of [`FullTypeDeclaration`]. Instead of materializing an owned
+/// tree up front, types are resolved on demand through the [`UnifiedProgramABI`] the
+/// view was created from. Useful for read-only traversals of large ABIs.
+#[derive(Debug, Clone, Copy)]
+pub struct FullTypeDeclarationRef<'a> {
+    type_decl: &'a UnifiedTypeDeclaration,
+    abi: &'a UnifiedProgramABI,
+}
+
+impl<'a> FullTypeDeclarationRef<'a> {
+    /// Creates a view of the type with `type_id` in `abi`.
+    pub fn new(abi: &'a UnifiedProgramABI, type_id: usize) -> Result<Self> {
+        let type_decl = abi.type_by_id(type_id).ok_or(Error::MissingType(type_id))?;
+
+        Ok(Self { type_decl, abi })
+    }
+
+    pub fn type_field(&self) -> &'a str {
+        &self.type_decl.type_field
+    }
+
+    pub fn components(&self) -> impl Iterator<Item = FullTypeApplicationRef<'a>> + 'a {
+        let abi = self.abi;
+        self.type_decl
+            .components
+            .iter()
+            .flatten()
+            .map(move |application| FullTypeApplicationRef { application, abi })
+    }
+
+    pub fn type_parameters(&self) -> impl Iterator<Item = Result<FullTypeDeclarationRef<'a>>> + 'a {
+        let abi = self.abi;
+        self.type_decl
+            .type_parameters
+            .iter()
+            .flatten()
+            .map(move |&type_id| FullTypeDeclarationRef::new(abi, type_id))
+    }
+
+    pub fn custom_type_path(&self) -> Result<TypePath> {
+        self.type_decl.custom_type_path()
+    }
+
+    pub fn is_custom_type(&self) -> bool {
+        self.type_decl.is_custom_type()
+    }
+
+    pub fn is_enum_type(&self) -> bool {
+        self.type_decl.is_enum_type()
+    }
+
+    pub fn is_struct_type(&self) -> bool {
+        self.type_decl.is_struct_type()
+    }
+
+    /// Materializes the owned [`FullTypeDeclaration`] this view represents.
+    pub fn to_full(&self) -> Result<FullTypeDeclaration> {
+        full_type_declaration(self.type_decl, &self.abi.type_lookup(), &mut vec![])
+    }
+}
+
+/// Borrowed counterpart of [`FullTypeApplication`], see [`FullTypeDeclarationRef`].
+#[derive(Debug, Clone, Copy)]
+pub struct FullTypeApplicationRef<'a> {
+    application: &'a UnifiedTypeApplication,
+    abi: &'a UnifiedProgramABI,
+}
+
+impl<'a> FullTypeApplicationRef<'a> {
+    pub fn name(&self) -> &'a str {
+        &self.application.name
+    }
+
+    pub fn type_decl(&self) -> Result<FullTypeDeclarationRef<'a>> {
+        FullTypeDeclarationRef::new(self.abi, self.application.type_id)
+    }
+
+    pub fn type_arguments(&self) -> impl Iterator<Item = FullTypeApplicationRef<'a>> + 'a {
+        let abi = self.abi;
+        self.application
+            .type_arguments
+            .iter()
+            .flatten()
+            .map(move |application| FullTypeApplicationRef { application, abi })
+    }
+
+    /// Materializes the owned [`FullTypeApplication`] this view represents.
+    pub fn to_full(&self) -> Result<FullTypeApplication> {
+        full_type_application(self.application, &self.abi.type_lookup(), &mut vec![])
+    }
+}
+
+/// `parents` holds the ids of the declarations being converted, so that a type
+/// containing itself is reported instead of recursing forever.
+fn full_type_declaration(
+    type_decl: &UnifiedTypeDeclaration,
+    types: &HashMap<usize, &UnifiedTypeDeclaration>,
+    parents: &mut Vec<usize>,
+) -> Result<FullTypeDeclaration> {
+    if parents.contains(&type_decl.type_id) {
+        return Err(Error::RecursiveType(type_decl.type_field.clone()));
+    }
+
+    let resolve = |type_id| types.get(&type_id).ok_or(Error::MissingType(type_id));
+
+    parents.push(type_decl.type_id);
+    let components = type_decl
+        .components
+        .iter()
+        .flatten()
+        .map(|component| full_type_application(component, types, parents))
+        .collect::<Result<_>>();
+    let type_parameters = type_decl
+        .type_parameters
+        .iter()
+        .flatten()
+        .map(|&type_id| full_type_declaration(resolve(type_id)?, types, parents))
+        .collect::<Result<_>>();
+    parents.pop();
+
+    Ok(FullTypeDeclaration {
+        type_field: type_decl.type_field.clone(),
+        components: components?,
+        type_parameters: type_parameters?,
+    })
+}
+
+fn full_type_application(
+    application: &UnifiedTypeApplication,
+    types: &HashMap<usize, &UnifiedTypeDeclaration>,
+    parents: &mut Vec<usize>,
+) -> Result<FullTypeApplication> {
+    let type_decl = types
+        .get(&application.type_id)
+        .ok_or(Error::MissingType(application.type_id))?;
+
+    Ok(FullTypeApplication {
+        name: application.name.clone(),
+        type_decl: full_type_declaration(type_decl, types, parents)?,
+        type_arguments: application
+            .type_arguments
+            .iter()
+            .flatten()
+            .map(|argument| full_type_application(argument, types, parents))
+            .collect::<Result<_>>()?,
+    })
+}
+
 impl FullTypeDeclaration {
+    pub fn is_unit_type(&self) -> bool {
+        self.type_field == "()"
+    }
+
     pub fn is_custom_type(&self) -> bool {
         self.is_struct_type() || self.is_enum_type()
     }
@@ -289,6 +1465,68 @@ impl FullTypeDeclaration {
     pub fn is_struct_type(&self) -> bool {
         self.type_field.starts_with("struct ")
     }
+
+    /// Pairs the names of a struct's fields, or an enum's variants, with their types.
+    /// Synthetic component names such as `__array_element` and `__tuple_element` are
+    /// skipped, and types other than structs and enums have no named fields.
+    pub fn named_fields(&self) -> Vec<(&str, &FullTypeApplication)> {
+        if !self.is_struct_type() && !self.is_enum_type() {
+            return vec![];
+        }
+
+        self.components
+            .iter()
+            .filter(|component| !is_synthetic_component_name(&component.name))
+            .map(|component| (component.name.as_str(), component))
+            .collect()
+    }
+
+    /// Returns the names of the type's generic parameters, in declaration order.
+    /// Parameters that aren't generics are skipped.
+    pub fn generic_param_names(&self) -> Vec<String> {
+        self.type_parameters
+            .iter()
+            .filter_map(|param| extract_generic_name(&param.type_field))
+            .collect()
+    }
+
+    /// Returns which std enum this type is, if any. Only the full `std` paths are
+    /// recognized, so user defined enums called `Option` or `Result` are not matched.
+    pub fn std_enum_kind(&self) -> Option<StdEnum> {
+        if !self.is_enum_type() {
+            return None;
+        }
+
+        let path = self.custom_type_path().ok()?.to_string();
+        match path.as_str() {
+            "std::option::Option" => Some(StdEnum::Option),
+            "std::result::Result" => Some(StdEnum::Result),
+            _ => None,
+        }
+    }
+
+    /// Returns the discriminant of the enum variant called `name`. Variants are
+    /// numbered in declaration order. Returns `None` for non-enum types.
+    pub fn enum_variant_index(&self, name: &str) -> Option<u64> {
+        if !self.is_enum_type() {
+            return None;
+        }
+
+        self.components
+            .iter()
+            .position(|variant| variant.name == name)
+            .and_then(|index| u64::try_from(index).ok())
+    }
+
+    /// Returns the enum variant with the discriminant `index`. Returns `None` for
+    /// non-enum types.
+    pub fn enum_variant_by_index(&self, index: u64) -> Option<&FullTypeApplication> {
+        if !self.is_enum_type() {
+            return None;
+        }
+
+        self.components.get(usize::try_from(index).ok()?)
+    }
 }
 
 #[cfg(test)]
@@ -296,6 +1534,10 @@ mod tests {
     use std::collections::HashMap;
 
     use super::*;
+    use crate::{
+        abi::program::{ABIFunction, ConcreteTypeId, ProgramABI, TypeConcreteDeclaration},
+        test_utils::{appl, decl, full_abi},
+    };
 
     #[test]
     fn abi_function_cannot_have_an_empty_name() {
@@ -314,7 +1556,1159 @@ mod tests {
 
         assert_eq!(err.to_string(), "FullABIFunction's name cannot be empty!");
     }
+
+    #[test]
+    fn deserialized_abi_function_cannot_have_an_empty_name() {
+        let function = FullABIFunction::new(
+            "some_fn".to_string(),
+            vec![],
+            appl("", decl("()", vec![])),
+            vec![],
+        )
+        .unwrap();
+        let json = serde_json::to_string(&function).unwrap();
+        assert_eq!(
+            serde_json::from_str::<FullABIFunction>(&json).unwrap(),
+            function
+        );
+
+        let err = serde_json::from_str::<FullABIFunction>(&json.replace("some_fn", ""))
+            .expect_err("should have failed");
+
+        assert!(err
+            .to_string()
+            .contains("FullABIFunction's name cannot be empty!"));
+    }
+
+    #[test]
+    fn json_abi_survives_a_round_trip() {
+        let abi = FullProgramABI::from_json_abi(
+            r#"{
+                "programType": "contract",
+                "specVersion": "1",
+                "encodingVersion": "1",
+                "concreteTypes": [
+                    {"type": "u64", "concreteTypeId": "u64"},
+                    {"type": "bool", "concreteTypeId": "bool"},
+                    {
+                        "type": "enum std::option::Option<u64>",
+                        "concreteTypeId": "option_u64",
+                        "metadataTypeId": 0,
+                        "typeArguments": ["u64"]
+                    },
+                    {
+                        "type": "struct Wrapper<bool>",
+                        "concreteTypeId": "wrapper_bool",
+                        "metadataTypeId": 2,
+                        "typeArguments": ["bool"]
+                    }
+                ],
+                "metadataTypes": [
+                    {
+                        "type": "enum std::option::Option",
+                        "metadataTypeId": 0,
+                        "components": [
+                            {"name": "None", "typeId": 3},
+                            {"name": "Some", "typeId": 1}
+                        ],
+                        "typeParameters": [1]
+                    },
+                    {"type": "generic T", "metadataTypeId": 1},
+                    {
+                        "type": "struct Wrapper",
+                        "metadataTypeId": 2,
+                        "components": [
+                            {
+                                "name": "inner",
+                                "typeId": 0,
+                                "typeArguments": [{"name": "", "typeId": 1}]
+                            }
+                        ],
+                        "typeParameters": [1]
+                    },
+                    {"type": "()", "metadataTypeId": 3}
+                ],
+                "functions": [
+                    {
+                        "name": "wrap",
+                        "inputs": [{"name": "value", "concreteTypeId": "option_u64"}],
+                        "output": "wrapper_bool",
+                        "attributes": [{"name": "storage", "arguments": ["read"]}]
+                    }
+                ],
+                "loggedTypes": [{"logId": "42", "concreteTypeId": "option_u64"}],
+                "messagesTypes": [],
+                "configurables": [
+                    {"name": "FLAG", "concreteTypeId": "bool", "offset": 96, "indirect": false}
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let round_tripped = FullProgramABI::from_json_abi(&abi.to_json_abi().unwrap()).unwrap();
+
+        assert_eq!(round_tripped, abi);
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn binary_abi_survives_a_round_trip() {
+        let function = FullABIFunction::new(
+            "main".to_string(),
+            vec![appl(
+                "arg",
+                decl("struct SomeStruct", vec![appl("a", decl("u8", vec![]))]),
+            )],
+            appl("", decl("()", vec![])),
+            vec![],
+        )
+        .unwrap();
+        let abi = FullProgramABI {
+            program_type: "script".to_string(),
+            functions: vec![function],
+            ..full_abi()
+        };
+
+        let bytes = abi.to_bytes().unwrap();
+
+        assert_eq!(FullProgramABI::from_bytes(&bytes).unwrap(), abi);
+        FullProgramABI::from_bytes(&bytes[..bytes.len() - 1]).expect_err("should have failed");
+    }
+
+    #[test]
+    fn can_convert_through_every_tier() {
+        fn to_full<'a, T>(abi: &'a T) -> Result<FullProgramABI>
+        where
+            UnifiedProgramABI: TryFrom<&'a T, Error = Error>,
+        {
+            let unified = UnifiedProgramABI::try_from(abi)?;
+            FullProgramABI::try_from(&unified)
+        }
+
+        let program_abi = ProgramABI {
+            program_type: "contract".to_string(),
+            spec_version: "1".into(),
+            encoding_version: "1".into(),
+            concrete_types: vec![TypeConcreteDeclaration {
+                type_field: "u64".to_string(),
+                concrete_type_id: ConcreteTypeId::from("u64_id"),
+                metadata_type_id: None,
+                type_arguments: None,
+            }],
+            functions: vec![ABIFunction {
+                inputs: vec![],
+                name: "some_fn".to_string(),
+                output: ConcreteTypeId::from("u64_id"),
+                attributes: None,
+            }],
+            ..Default::default()
+        };
+
+        let full_abi = to_full(&program_abi).expect("conversion should succeed");
+
+        assert_eq!(full_abi.functions[0].name(), "some_fn");
+        assert_eq!(full_abi.functions[0].output().type_decl.type_field, "u64");
+    }
+
+    #[test]
+    fn detects_unit_returning_functions() {
+        let output = |type_field: &str| FullTypeApplication {
+            name: "".to_string(),
+            type_decl: FullTypeDeclaration {
+                type_field: type_field.to_string(),
+                components: vec![],
+                type_parameters: vec![],
+            },
+            type_arguments: vec![],
+        };
+
+        let returns_unit =
+            FullABIFunction::new("returns_unit".to_string(), vec![], output("()"), vec![]).unwrap();
+        let returns_u64 =
+            FullABIFunction::new("returns_u64".to_string(), vec![], output("u64"), vec![]).unwrap();
+
+        assert!(returns_unit.returns_unit());
+        assert!(!returns_u64.returns_unit());
+    }
+
+    #[test]
+    fn detects_unbound_generics() {
+        let type_decl = |type_field: &str| FullTypeDeclaration {
+            type_field: type_field.to_string(),
+            components: vec![],
+            type_parameters: vec![],
+        };
+        let application = |type_decl: FullTypeDeclaration, type_arguments| FullTypeApplication {
+            name: "".to_string(),
+            type_decl,
+            type_arguments,
+        };
+        let generic_struct = FullTypeDeclaration {
+            type_field: "struct SomeStruct".to_string(),
+            components: vec![application(type_decl("generic T"), vec![])],
+            type_parameters: vec![type_decl("generic T")],
+        };
+
+        let applied = application(
+            generic_struct.clone(),
+            vec![application(type_decl("u8"), vec![])],
+        );
+        let under_applied = application(generic_struct.clone(), vec![]);
+        let applied_with_generic = application(
+            generic_struct,
+            vec![application(type_decl("generic U"), vec![])],
+        );
+
+        assert!(!applied.has_unbound_generics());
+        assert!(under_applied.has_unbound_generics());
+        assert!(applied_with_generic.has_unbound_generics());
+        assert!(application(type_decl("generic T"), vec![]).has_unbound_generics());
+        assert!(!application(type_decl("u64"), vec![]).has_unbound_generics());
+    }
+
+    #[test]
+    fn configurable_offsets_are_validated_against_binary_len() {
+        let configurable = |name: &str, type_field: &str, offset, indirect| FullConfigurable {
+            name: name.to_string(),
+            application: FullTypeApplication {
+                name: "".to_string(),
+                type_decl: FullTypeDeclaration {
+                    type_field: type_field.to_string(),
+                    components: vec![],
+                    type_parameters: vec![],
+                },
+                type_arguments: vec![],
+            },
+            offset,
+            indirect,
+        };
+        let abi = FullProgramABI {
+            configurables: vec![
+                configurable("U64", "u64", 0, false),
+                configurable("B256", "b256", 8, false),
+                configurable("VEC", "struct std::vec::Vec", 40, true),
+            ],
+            ..full_abi()
+        };
+
+        abi.validate_configurable_offsets(48)
+            .expect("all configurables should fit");
+
+        let err = abi
+            .validate_configurable_offsets(47)
+            .expect_err("should have failed");
+        assert_eq!(
+            err.to_string(),
+            "configurable 'VEC' at offset 40 with size 8 does not fit into a binary of 47 bytes"
+        );
+    }
+
+    #[test]
+    fn composite_configurables_must_fit_entirely() {
+        let configurable = |name: &str, type_decl| FullConfigurable {
+            name: name.to_string(),
+            application: appl("", type_decl),
+            offset: 8,
+            indirect: false,
+        };
+        let a_struct = decl(
+            "struct SomeStruct",
+            vec![
+                appl("a", decl("u64", vec![])),
+                appl("b", decl("b256", vec![])),
+            ],
+        );
+        let array = decl("[_; 4]", vec![appl("__array_element", decl("u64", vec![]))]);
+        let abi_with = |configurable| FullProgramABI {
+            configurables: vec![configurable],
+            ..full_abi()
+        };
+
+        let struct_abi = abi_with(configurable("STRUCT", a_struct));
+        struct_abi
+            .validate_configurable_offsets(48)
+            .expect("the struct should fit");
+        let err = struct_abi
+            .validate_configurable_offsets(47)
+            .expect_err("should have failed");
+        assert_eq!(
+            err.to_string(),
+            "configurable 'STRUCT' at offset 8 with size 40 does not fit into a binary of 47 bytes"
+        );
+
+        abi_with(configurable("ARRAY", array))
+            .validate_configurable_offsets(39)
+            .expect_err("should have failed");
+    }
+
+    #[test]
+    fn configurables_of_unknown_size_must_start_within_the_binary() {
+        let abi = FullProgramABI {
+            configurables: vec![FullConfigurable {
+                name: "NAME".to_string(),
+                application: appl("", decl("struct std::string::String", vec![])),
+                offset: 8,
+                indirect: false,
+            }],
+            ..full_abi()
+        };
+        assert_eq!(abi.configurables[0].application.encoded_size(), None);
+
+        abi.validate_configurable_offsets(9)
+            .expect("the offset is within the binary");
+        let err = abi
+            .validate_configurable_offsets(8)
+            .expect_err("should have failed");
+        assert_eq!(
+            err.to_string(),
+            "configurable 'NAME' at offset 8 is beyond the end of a binary of 8 bytes"
+        );
+    }
+
+    #[test]
+    fn encoded_size_of_primitives() {
+        for (type_field, size) in [
+            ("()", 0),
+            ("bool", 1),
+            ("u8", 1),
+            ("u16", 2),
+            ("u32", 4),
+            ("u64", 8),
+            ("u256", 32),
+            ("b256", 32),
+            ("str[5]", 5),
+        ] {
+            assert_eq!(decl(type_field, vec![]).encoded_size(), Some(size));
+        }
+    }
+
+    #[test]
+    fn encoded_size_of_composite_types() {
+        let u8_field = appl("a", decl("u8", vec![]));
+        let u64_field = appl("b", decl("u64", vec![]));
+
+        let array = decl("[_; 3]", vec![appl("__array_element", decl("u64", vec![]))]);
+        let tuple = decl("(_, _)", vec![u8_field.clone(), u64_field.clone()]);
+        let a_struct = decl(
+            "struct SomeStruct",
+            vec![u8_field.clone(), appl("c", array.clone())],
+        );
+        let same_sized_enum = decl("enum SomeEnum", vec![u64_field.clone(), u64_field.clone()]);
+        let variable_sized_enum = decl("enum SomeEnum", vec![u8_field, u64_field]);
+
+        assert_eq!(array.encoded_size(), Some(24));
+        assert_eq!(tuple.encoded_size(), Some(9));
+        assert_eq!(a_struct.encoded_size(), Some(25));
+        assert_eq!(same_sized_enum.encoded_size(), Some(16));
+        assert_eq!(variable_sized_enum.encoded_size(), None);
+    }
+
+    #[test]
+    fn encoded_size_of_enums_with_variants_of_different_sizes_is_unknown() {
+        let option_of_u64 = FullTypeApplication {
+            name: "".to_string(),
+            type_decl: FullTypeDeclaration {
+                type_parameters: vec![decl("generic T", vec![])],
+                ..decl(
+                    "enum std::option::Option",
+                    vec![
+                        appl("None", decl("()", vec![])),
+                        appl("Some", decl("generic T", vec![])),
+                    ],
+                )
+            },
+            type_arguments: vec![appl("", decl("u64", vec![]))],
+        };
+        let option_of_unit = FullTypeApplication {
+            type_arguments: vec![appl("", decl("()", vec![]))],
+            ..option_of_u64.clone()
+        };
+
+        assert_eq!(option_of_u64.encoded_size(), None);
+        assert_eq!(option_of_unit.encoded_size(), Some(8));
+    }
+
+    #[test]
+    fn debug_summary_is_truncated_beyond_max_depth() {
+        let inner = decl("struct Inner", vec![appl("flag", decl("bool", vec![]))]);
+        let outer = decl(
+            "struct Outer",
+            vec![appl("a", decl("u8", vec![])), appl("inner", inner)],
+        );
+        let option_of_outer = FullTypeApplication {
+            name: "".to_string(),
+            type_decl: decl(
+                "enum std::option::Option",
+                vec![
+                    appl("None", decl("()", vec![])),
+                    appl("Some", decl("generic T", vec![])),
+                ],
+            ),
+            type_arguments: vec![appl("", outer.clone())],
+        };
+
+        assert_eq!(outer.debug_summary(0), "struct Outer { ... }");
+        assert_eq!(
+            outer.debug_summary(1),
+            "struct Outer { a: u8, inner: struct Inner { ... } }"
+        );
+        assert_eq!(
+            outer.debug_summary(5),
+            "struct Outer { a: u8, inner: struct Inner { flag: bool } }"
+        );
+        assert_eq!(
+            option_of_outer.debug_summary(0),
+            "enum std::option::Option<...> { ... }"
+        );
+        assert_eq!(
+            option_of_outer.debug_summary(1),
+            "enum std::option::Option<struct Outer { ... }> { None: (), Some: generic T }"
+        );
+    }
+
+    #[test]
+    fn functions_returning_uninhabited_types_cannot_return() {
+        let function = |output| {
+            FullABIFunction::new("main".to_string(), vec![], appl("", output), vec![]).unwrap()
+        };
+        let empty_enum = decl("enum Never", vec![]);
+
+        assert!(empty_enum.is_never_returning());
+        assert!(decl("!", vec![]).is_never_returning());
+        assert!(!decl("struct Empty", vec![]).is_never_returning());
+        assert!(!function(empty_enum.clone()).can_return_normally());
+        assert!(function(decl("()", vec![])).can_return_normally());
+
+        let err = appl("", empty_enum)
+            .decode(&[0; 8])
+            .expect_err("should have failed");
+        assert_eq!(
+            err.to_string(),
+            "cannot decode 'enum Never', it has no values"
+        );
+    }
+
+    #[test]
+    fn zero_sized_types() {
+        let unit = decl("()", vec![]);
+        let empty_struct = decl("struct Empty", vec![]);
+        let struct_of_units = decl("struct Units", vec![appl("a", unit.clone())]);
+        let non_empty_struct = decl("struct SomeStruct", vec![appl("a", decl("u8", vec![]))]);
+        let unit_enum = decl("enum SomeEnum", vec![appl("A", unit.clone())]);
+
+        assert!(unit.is_zero_sized());
+        assert!(empty_struct.is_zero_sized());
+        assert!(struct_of_units.is_zero_sized());
+        assert!(!non_empty_struct.is_zero_sized());
+        assert!(!unit_enum.is_zero_sized());
+    }
+
+    #[test]
+    fn b512_is_64_bytes_long() {
+        let b256_pair = decl(
+            "[_; 2]",
+            vec![appl("__array_element", decl("b256", vec![]))],
+        );
+        let b512 = decl("struct std::b512::B512", vec![appl("bits", b256_pair)]);
+        let opaque_b512 = decl("struct std::b512::B512", vec![]);
+
+        assert!(b512.is_b512());
+        assert_eq!(b512.encoded_size(), Some(64));
+        assert_eq!(opaque_b512.encoded_size(), Some(64));
+        assert_eq!(b512.canonical_path(), "std::b512::B512");
+        assert!(!decl("struct lib::B512", vec![]).is_b512());
+        assert_eq!(b512.std_short_name(), Some("B512"));
+        assert_eq!(decl("struct lib::B512", vec![]).std_short_name(), None);
+        assert_eq!(decl("b256", vec![]).std_short_name(), None);
+    }
+
+    #[test]
+    fn heap_types_have_no_encoded_size() {
+        let vec = decl(
+            "struct std::vec::Vec",
+            vec![appl("len", decl("u64", vec![]))],
+        );
+
+        assert_eq!(vec.encoded_size(), None);
+        assert_eq!(decl("str", vec![]).encoded_size(), None);
+        assert_eq!(decl("raw untyped slice", vec![]).encoded_size(), None);
+    }
+
+    #[test]
+    fn encoded_size_of_generic_types_needs_type_arguments() {
+        let option = FullTypeDeclaration {
+            type_field: "enum std::option::Option".to_string(),
+            components: vec![
+                appl("None", decl("()", vec![])),
+                appl("Some", decl("generic T", vec![])),
+            ],
+            type_parameters: vec![decl("generic T", vec![])],
+        };
+        let option_of_u64 = FullTypeApplication {
+            name: "".to_string(),
+            type_decl: option.clone(),
+            type_arguments: vec![appl("", decl("u64", vec![]))],
+        };
+        let option_of_unit = FullTypeApplication {
+            type_arguments: vec![appl("", decl("()", vec![]))],
+            ..option_of_u64.clone()
+        };
+
+        assert_eq!(option.encoded_size(), None);
+        assert_eq!(option_of_u64.encoded_size(), None);
+        assert_eq!(option_of_unit.encoded_size(), Some(8));
+
+        let wrapper = decl(
+            "struct Wrapper",
+            vec![FullTypeApplication {
+                name: "inner".to_string(),
+                ..option_of_unit
+            }],
+        );
+        assert_eq!(wrapper.encoded_size(), Some(8));
+    }
+
+    #[test]
+    fn enum_variants_are_indexed_in_declaration_order() {
+        let an_enum = decl(
+            "enum SomeEnum",
+            vec![
+                appl("A", decl("()", vec![])),
+                appl("B", decl("u64", vec![])),
+            ],
+        );
+
+        assert_eq!(an_enum.enum_variant_index("A"), Some(0));
+        assert_eq!(an_enum.enum_variant_index("B"), Some(1));
+        assert_eq!(an_enum.enum_variant_index("C"), None);
+        assert_eq!(an_enum.enum_variant_by_index(1).unwrap().name, "B");
+        assert!(an_enum.enum_variant_by_index(2).is_none());
+
+        let a_struct = decl("struct SomeStruct", an_enum.components.clone());
+        assert_eq!(a_struct.enum_variant_index("A"), None);
+        assert!(a_struct.enum_variant_by_index(0).is_none());
+    }
+
+    #[test]
+    fn logged_and_message_types_can_be_deduplicated() {
+        let application = appl("", decl("u64", vec![]));
+        let logged_type = FullLoggedType {
+            log_id: LogId(0),
+            application: application.clone(),
+        };
+        let message_type = FullMessageType {
+            message_id: MessageId(0),
+            application: application.clone(),
+        };
+        let function =
+            FullABIFunction::new("some_fn".to_string(), vec![], application, vec![]).unwrap();
+
+        let logged_types: HashSet<_> = [logged_type.clone(), logged_type].into_iter().collect();
+        let message_types: HashSet<_> = [message_type.clone(), message_type].into_iter().collect();
+        let functions: HashSet<_> = [function.clone(), function].into_iter().collect();
+
+        assert_eq!(logged_types.len(), 1);
+        assert_eq!(message_types.len(), 1);
+        assert_eq!(functions.len(), 1);
+    }
+
+    #[test]
+    fn doc_comments_are_indexed_by_function() {
+        let doc = |line: &str| Attribute {
+            name: "doc-comment".to_string(),
+            arguments: vec![line.to_string()],
+        };
+        let function = |name: &str, attributes| {
+            FullABIFunction::new(
+                name.to_string(),
+                vec![],
+                appl("", decl("()", vec![])),
+                attributes,
+            )
+            .unwrap()
+        };
+        let mut abi = FullProgramABI {
+            functions: vec![
+                function(
+                    "documented",
+                    vec![doc(" First line."), doc(" Second line.")],
+                ),
+                function("undocumented", vec![]),
+            ],
+            ..full_abi()
+        };
+
+        let docs = abi.all_doc_comments().unwrap();
+
+        assert_eq!(
+            docs.functions,
+            BTreeMap::from([(
+                "documented".to_string(),
+                vec![" First line.".to_string(), " Second line.".to_string()]
+            )])
+        );
+
+        abi.functions[1].attributes.push(Attribute {
+            name: "doc-comment".to_string(),
+            arguments: vec![],
+        });
+        abi.all_doc_comments().expect_err("should have failed");
+    }
+
+    #[test]
+    fn attributes_can_be_stripped_for_comparison() {
+        let abi_with_doc = |doc: &str| FullProgramABI {
+            functions: vec![FullABIFunction::new(
+                "some_fn".to_string(),
+                vec![],
+                appl("", decl("()", vec![])),
+                vec![Attribute {
+                    name: "doc-comment".to_string(),
+                    arguments: vec![doc.to_string()],
+                }],
+            )
+            .unwrap()],
+            ..full_abi()
+        };
+
+        let abi = abi_with_doc("Does something.");
+        let abi_with_edited_doc = abi_with_doc("Does something else.");
+
+        assert_ne!(abi, abi_with_edited_doc);
+        assert_eq!(
+            abi.without_attributes(),
+            abi_with_edited_doc.without_attributes()
+        );
+        assert!(abi.without_attributes().functions[0]
+            .doc_strings()
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn configurable_offsets_can_be_ignored_for_comparison() {
+        let abi_with_offset = |offset: u64, type_field: &str| FullProgramABI {
+            configurables: vec![FullConfigurable {
+                name: "SOME_CONFIGURABLE".to_string(),
+                application: appl("", decl(type_field, vec![])),
+                offset,
+                indirect: false,
+            }],
+            ..full_abi()
+        };
+
+        let abi = abi_with_offset(8, "u64");
+        let relinked_abi = abi_with_offset(16, "u64");
+
+        assert_ne!(abi, relinked_abi);
+        assert!(abi.eq_ignoring_configurable_offsets(&relinked_abi));
+        assert_eq!(
+            relinked_abi.without_configurable_offsets().configurables[0].offset,
+            0
+        );
+        assert!(!abi.eq_ignoring_configurable_offsets(&abi_with_offset(8, "u32")));
+    }
+
+    #[test]
+    fn entry_points_depend_on_program_type() {
+        let function = |name: &str| {
+            FullABIFunction::new(
+                name.to_string(),
+                vec![],
+                appl("", decl("()", vec![])),
+                vec![],
+            )
+            .unwrap()
+        };
+        let abi_of = |program_type: &str| FullProgramABI {
+            program_type: program_type.to_string(),
+            functions: vec![function("main"), function("helper")],
+            ..full_abi()
+        };
+        let names = |abi: &FullProgramABI| {
+            abi.entry_points()
+                .iter()
+                .map(|function| function.name().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(names(&abi_of("contract")), ["main", "helper"]);
+        assert_eq!(names(&abi_of("script")), ["main"]);
+        assert_eq!(names(&abi_of("predicate")), ["main"]);
+        assert!(names(&abi_of("library")).is_empty());
+
+        assert!(abi_of("predicate").is_predicate());
+        assert!(!abi_of("predicate").is_contract());
+        assert!(abi_of("contract").is_contract());
+        assert!(abi_of("script").is_script());
+        assert!(abi_of("library").is_library());
+        assert!(!abi_of("something else").is_predicate());
+    }
+
+    #[test]
+    fn functions_can_be_looked_up_by_name() {
+        let function = |name: &str| {
+            FullABIFunction::new(
+                name.to_string(),
+                vec![],
+                appl("", decl("()", vec![])),
+                vec![],
+            )
+            .unwrap()
+        };
+        let abi = FullProgramABI {
+            functions: vec![function("first"), function("second")],
+            ..full_abi()
+        };
+
+        assert_eq!(
+            abi.iter_functions()
+                .map(FullABIFunction::name)
+                .collect::<Vec<_>>(),
+            ["first", "second"]
+        );
+        assert_eq!(abi.get_function("second"), Some(&function("second")));
+        assert_eq!(abi.get_function("third"), None);
+    }
+
+    #[test]
+    fn instantiations_of_generic_types_are_listed() {
+        let foo_of = |type_field: &str| FullTypeApplication {
+            name: "".to_string(),
+            type_decl: FullTypeDeclaration {
+                type_field: "struct lib::Foo".to_string(),
+                components: vec![appl("value", decl("generic T", vec![]))],
+                type_parameters: vec![decl("generic T", vec![])],
+            },
+            type_arguments: vec![appl("", decl(type_field, vec![]))],
+        };
+        let bar = appl(
+            "bar",
+            decl(
+                "struct lib::Bar",
+                vec![FullTypeApplication {
+                    name: "inner".to_string(),
+                    ..foo_of("u8")
+                }],
+            ),
+        );
+        let function = |name: &str, input: FullTypeApplication, output| {
+            FullABIFunction::new(name.to_string(), vec![input], output, vec![]).unwrap()
+        };
+        let abi = FullProgramABI {
+            functions: vec![
+                function("first", foo_of("u8"), foo_of("u64")),
+                function("second", bar, appl("", decl("()", vec![]))),
+            ],
+            logged_types: vec![FullLoggedType {
+                log_id: LogId(1),
+                application: foo_of("u64"),
+            }],
+            ..full_abi()
+        };
+
+        let instantiations = abi.instantiations_of(&TypePath::new("lib::Foo").unwrap());
+
+        assert_eq!(
+            instantiations,
+            [
+                vec![appl("", decl("u8", vec![]))],
+                vec![appl("", decl("u64", vec![]))]
+            ]
+        );
+        assert!(abi
+            .instantiations_of(&TypePath::new("lib::Bar").unwrap())
+            .is_empty());
+    }
+
+    #[test]
+    fn functions_with_heap_types_are_listed() {
+        let u8_decl = decl("u8", vec![]);
+        let vec_of_u8 = FullTypeApplication {
+            name: "".to_string(),
+            type_decl: FullTypeDeclaration {
+                type_field: "struct std::vec::Vec".to_string(),
+                components: vec![],
+                type_parameters: vec![decl("generic T", vec![])],
+            },
+            type_arguments: vec![appl("", u8_decl.clone())],
+        };
+        let option_of_vec = FullTypeApplication {
+            name: "".to_string(),
+            type_decl: FullTypeDeclaration {
+                type_field: "enum std::option::Option".to_string(),
+                components: vec![
+                    appl("None", decl("()", vec![])),
+                    appl("Some", decl("generic T", vec![])),
+                ],
+                type_parameters: vec![decl("generic T", vec![])],
+            },
+            type_arguments: vec![vec_of_u8],
+        };
+        let function = |name: &str, input: FullTypeApplication| {
+            FullABIFunction::new(
+                name.to_string(),
+                vec![input],
+                appl("", decl("()", vec![])),
+                vec![],
+            )
+            .unwrap()
+        };
+        let abi = FullProgramABI {
+            functions: vec![
+                function("takes_u8", appl("arg", u8_decl)),
+                function("takes_option_of_vec", option_of_vec),
+            ],
+            ..full_abi()
+        };
+
+        let names: Vec<_> = abi
+            .functions_with_heap_types()
+            .into_iter()
+            .map(FullABIFunction::name)
+            .collect();
+
+        assert_eq!(names, ["takes_option_of_vec"]);
+    }
+
+    #[test]
+    fn tooling_attributes_are_exposed() {
+        let function = |attributes: Vec<(&str, Vec<&str>)>| {
+            let attributes = attributes
+                .into_iter()
+                .map(|(name, arguments)| Attribute {
+                    name: name.to_string(),
+                    arguments: arguments.into_iter().map(String::from).collect(),
+                })
+                .collect();
+            FullABIFunction::new(
+                "some_fn".to_string(),
+                vec![],
+                appl("", decl("()", vec![])),
+                attributes,
+            )
+            .unwrap()
+        };
+
+        let test_fn = function(vec![("test", vec![])]);
+        assert!(test_fn.is_test());
+        assert_eq!(test_fn.inline_hint(), None);
+
+        let always_inlined = function(vec![("inline", vec!["always"])]);
+        assert!(!always_inlined.is_test());
+        assert_eq!(always_inlined.inline_hint(), Some(InlineKind::Always));
+
+        let never_inlined = function(vec![("inline", vec!["never"])]);
+        assert_eq!(never_inlined.inline_hint(), Some(InlineKind::Never));
+
+        let malformed = function(vec![("inline", vec!["always", "never"])]);
+        assert_eq!(malformed.inline_hint(), None);
+
+        let deposit = function(vec![
+            ("payable", vec![]),
+            ("storage", vec!["read", "write"]),
+        ]);
+        assert_eq!(
+            deposit.call_requirements(),
+            CallRequirements {
+                payable: true,
+                reads_storage: true,
+                writes_storage: true,
+            }
+        );
+        assert!(!deposit.call_requirements().is_payable_read_only());
+
+        let read_only = function(vec![("payable", vec![]), ("storage", vec!["read"])]);
+        assert!(read_only.call_requirements().is_payable_read_only());
+        assert_eq!(test_fn.call_requirements(), CallRequirements::default());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn borrowed_view_matches_owned_conversion() {
+        let abi = UnifiedProgramABI {
+            types: vec![
+                UnifiedTypeDeclaration {
+                    type_id: 0,
+                    type_field: "struct SomeStruct".to_string(),
+                    components: Some(vec![UnifiedTypeApplication {
+                        name: "field".to_string(),
+                        type_id: 1,
+                        type_arguments: None,
+                    }]),
+                    type_parameters: Some(vec![1]),
+                },
+                UnifiedTypeDeclaration {
+                    type_id: 1,
+                    type_field: "generic T".to_string(),
+                    components: None,
+                    type_parameters: None,
+                },
+            ],
+            ..Default::default()
+        };
+        let lookup = abi.owned_type_lookup();
+
+        let view = FullTypeDeclarationRef::new(&abi, 0).unwrap();
+
+        assert!(view.is_struct_type());
+        assert_eq!(view.type_field(), "struct SomeStruct");
+        let component = view.components().next().unwrap();
+        assert_eq!(component.name(), "field");
+        assert_eq!(component.type_decl().unwrap().type_field(), "generic T");
+        assert_eq!(
+            view.to_full().unwrap(),
+            FullTypeDeclaration::from_counterpart(&abi.types[0], &lookup)
+        );
+    }
+
+    #[test]
+    fn borrowed_view_reports_missing_types() {
+        let abi = UnifiedProgramABI::default();
+
+        let err = FullTypeDeclarationRef::new(&abi, 7).expect_err("should have failed");
+
+        assert!(matches!(err, Error::MissingType(7)));
+    }
+
+    #[test]
+    fn self_referencing_types_are_rejected() {
+        let abi = FullProgramABI::from_json_abi(
+            r#"{
+                "programType": "contract",
+                "specVersion": "1",
+                "encodingVersion": "1",
+                "concreteTypes": [
+                    {"type": "struct Node", "concreteTypeId": "node", "metadataTypeId": 0}
+                ],
+                "metadataTypes": [
+                    {
+                        "type": "struct Node",
+                        "metadataTypeId": 0,
+                        "components": [{"name": "next", "typeId": 0}]
+                    }
+                ],
+                "functions": [
+                    {"name": "main", "inputs": [], "output": "node", "attributes": null}
+                ],
+                "loggedTypes": [],
+                "messagesTypes": [],
+                "configurables": []
+            }"#,
+        );
+
+        let err = abi.expect_err("should have failed");
+
+        assert!(matches!(&err, Error::RecursiveType(type_field) if type_field == "struct Node"));
+        assert_eq!(err.to_string(), "type 'struct Node' refers to itself");
+    }
+
+    #[test]
+    fn conversion_reports_missing_types() {
+        let abi = UnifiedProgramABI {
+            functions: vec![UnifiedABIFunction {
+                name: "main".to_string(),
+                inputs: vec![],
+                output: UnifiedTypeApplication {
+                    name: "".to_string(),
+                    type_id: 7,
+                    type_arguments: None,
+                },
+                attributes: None,
+            }],
+            ..Default::default()
+        };
+
+        let err = FullProgramABI::try_from(&abi).expect_err("should have failed");
+
+        assert!(matches!(err, Error::MissingType(7)));
+    }
+
+    #[test]
+    fn std_enums_are_detected_by_path() {
+        assert_eq!(
+            decl("enum std::option::Option", vec![]).std_enum_kind(),
+            Some(StdEnum::Option)
+        );
+        assert_eq!(
+            decl("enum std::result::Result", vec![]).std_enum_kind(),
+            Some(StdEnum::Result)
+        );
+        assert_eq!(decl("enum my_lib::Option", vec![]).std_enum_kind(), None);
+        assert_eq!(decl("enum Option", vec![]).std_enum_kind(), None);
+        assert_eq!(
+            decl("struct std::option::Option", vec![]).std_enum_kind(),
+            None
+        );
+    }
+
+    #[test]
+    fn named_fields_skip_synthetic_names() {
+        let u64_decl = decl("u64", vec![]);
+        let point = decl(
+            "struct Point",
+            vec![appl("x", u64_decl.clone()), appl("y", u64_decl.clone())],
+        );
+        let fields = point.named_fields();
+        assert_eq!(
+            fields.iter().map(|(name, _)| *name).collect::<Vec<_>>(),
+            ["x", "y"]
+        );
+        assert_eq!(fields[0].1.type_decl, u64_decl);
+
+        let array = decl("[_; 2]", vec![appl("__array_element", u64_decl.clone())]);
+        assert!(array.named_fields().is_empty());
+
+        let tuple = decl(
+            "(_, _)",
+            vec![
+                appl("__tuple_element", u64_decl.clone()),
+                appl("__tuple_element", u64_decl),
+            ],
+        );
+        assert!(tuple.named_fields().is_empty());
+    }
+
+    #[test]
+    fn applied_type_paths_include_generic_arguments() {
+        let u8_decl = decl("u8", vec![]);
+        let vec_decl = FullTypeDeclaration {
+            type_field: "struct std::vec::Vec".to_string(),
+            components: vec![],
+            type_parameters: vec![decl("generic T", vec![])],
+        };
+        let tuple = decl(
+            "(_, _)",
+            vec![
+                appl("__tuple_element", u8_decl.clone()),
+                appl(
+                    "__tuple_element",
+                    decl("[_; 2]", vec![appl("__array_element", u8_decl.clone())]),
+                ),
+            ],
+        );
+        let vec_of_tuples = FullTypeApplication {
+            name: "".to_string(),
+            type_decl: vec_decl,
+            type_arguments: vec![appl("", tuple)],
+        };
+
+        let primitive = || AppliedTypePath::Primitive("u8".to_string());
+        assert_eq!(
+            vec_of_tuples.applied_type_path().unwrap(),
+            AppliedTypePath::Custom {
+                path: TypePath::new("std::vec::Vec").unwrap(),
+                args: vec![AppliedTypePath::Tuple(vec![
+                    primitive(),
+                    AppliedTypePath::Array {
+                        element: Box::new(primitive()),
+                        len: 2,
+                    },
+                ])],
+            }
+        );
+        assert_eq!(
+            appl("", decl("()", vec![])).applied_type_path().unwrap(),
+            AppliedTypePath::Primitive("()".to_string())
+        );
+        assert_eq!(
+            appl("", decl("generic T", vec![]))
+                .applied_type_path()
+                .unwrap(),
+            AppliedTypePath::Generic("T".to_string())
+        );
+    }
+
+    #[test]
+    fn every_kind_of_type_has_a_canonical_path() {
+        let u8_decl = decl("u8", vec![]);
+        let tuple = decl(
+            "(_, _)",
+            vec![
+                appl("__tuple_element", u8_decl.clone()),
+                appl("__tuple_element", decl("u64", vec![])),
+            ],
+        );
+        let array = decl(
+            "[_; 3]",
+            vec![appl("__array_element", decl("b256", vec![]))],
+        );
+        let vec_decl = decl("struct std::vec::Vec", vec![]);
+        let nested = decl("[_; 2]", vec![appl("__array_element", tuple.clone())]);
+
+        assert_eq!(u8_decl.canonical_path(), "u8");
+        assert_eq!(decl("()", vec![]).canonical_path(), "()");
+        assert_eq!(decl("str[4]", vec![]).canonical_path(), "str[4]");
+        assert_eq!(tuple.canonical_path(), "(u8, u64)");
+        assert_eq!(array.canonical_path(), "[b256; 3]");
+        assert_eq!(nested.canonical_path(), "[(u8, u64); 2]");
+        assert_eq!(vec_decl.canonical_path(), "std::vec::Vec");
+        assert_eq!(decl("enum  SomeEnum", vec![]).canonical_path(), "SomeEnum");
+    }
+
+    #[test]
+    fn generic_param_names_are_listed_in_order() {
+        let generic_struct = FullTypeDeclaration {
+            type_field: "struct Pair".to_string(),
+            components: vec![],
+            type_parameters: vec![
+                decl("generic K", vec![]),
+                decl("generic V", vec![]),
+                decl("u64", vec![]),
+            ],
+        };
+
+        assert_eq!(generic_struct.generic_param_names(), ["K", "V"]);
+        assert!(decl("u64", vec![]).generic_param_names().is_empty());
+    }
+
+    #[test]
+    fn can_be_serialized_and_deserialized() {
+        let u64_type = FullTypeDeclaration {
+            type_field: "u64".to_string(),
+            components: vec![],
+            type_parameters: vec![],
+        };
+        let application = FullTypeApplication {
+            name: "".to_string(),
+            type_decl: u64_type.clone(),
+            type_arguments: vec![],
+        };
+        let abi = FullProgramABI {
+            types: vec![u64_type],
+            functions: vec![FullABIFunction::new(
+                "some_fn".to_string(),
+                vec![],
+                application.clone(),
+                vec![],
+            )
+            .unwrap()],
+            logged_types: vec![FullLoggedType {
+                log_id: LogId(0),
+                application: application.clone(),
+            }],
+            configurables: vec![FullConfigurable {
+                name: "SOME_CONFIGURABLE".to_string(),
+                application: application.clone(),
+                offset: 8,
+                indirect: false,
+            }],
+            storage_slots: vec![FullStorageSlot {
+                key: "0x01".to_string(),
+                value: "0x02".to_string(),
+                application: Some(application),
+            }],
+            ..full_abi()
+        };
+
+        let json = serde_json::to_string(&abi).unwrap();
+        let deserialized: FullProgramABI = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(deserialized, abi);
+    }
+
     #[test]
+    #[allow(deprecated)]
     fn can_convert_into_full_type_decl() {
         // given
         let type_0 = UnifiedTypeDeclaration {
@@ -383,6 +2777,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn can_convert_into_full_type_appl() {
         let application = UnifiedTypeApplication {
             name: "ta_0".to_string(),
@@ -0,0 +1,97 @@
+//! Lookup of logged types by their log id, for decoding large volumes of logs.
+
+use std::collections::HashMap;
+
+use crate::abi::{
+    full_program::{FullLoggedType, FullProgramABI},
+    program::LogId,
+};
+
+/// Index from log ids to the types they log, across one or more programs.
+///
+/// The index is built once and is `Send + Sync`, so a single instance can be shared
+/// between workers, e.g. behind an `Arc`. Programs are identified by their position in
+/// the ABIs the index was built from.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct LoggedTypeIndex {
+    logged_types: HashMap<(usize, LogId), FullLoggedType>,
+}
+
+impl LoggedTypeIndex {
+    /// Indexes the logged types of a single program, identified by `0`.
+    pub fn new(abi: &FullProgramABI) -> Self {
+        Self::from_abis(std::slice::from_ref(abi))
+    }
+
+    pub fn from_abis(abis: &[FullProgramABI]) -> Self {
+        let logged_types = abis
+            .iter()
+            .enumerate()
+            .flat_map(|(program, abi)| {
+                abi.logged_types
+                    .iter()
+                    .map(move |logged_type| ((program, logged_type.log_id), logged_type.clone()))
+            })
+            .collect();
+
+        Self { logged_types }
+    }
+
+    /// Returns the type logged under `log_id` by the `program`th program.
+    pub fn get(&self, program: usize, log_id: LogId) -> Option<&FullLoggedType> {
+        self.logged_types.get(&(program, log_id))
+    }
+
+    pub fn len(&self) -> usize {
+        self.logged_types.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.logged_types.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{appl, decl, full_abi};
+
+    fn abi(logged_types: Vec<(u64, &str)>) -> FullProgramABI {
+        FullProgramABI {
+            logged_types: logged_types
+                .into_iter()
+                .map(|(log_id, type_field)| FullLoggedType {
+                    log_id: LogId(log_id),
+                    application: appl("", decl(type_field, vec![])),
+                })
+                .collect(),
+            ..full_abi()
+        }
+    }
+
+    #[test]
+    fn logged_types_are_looked_up_per_program() {
+        let index = LoggedTypeIndex::from_abis(&[abi(vec![(1, "u64")]), abi(vec![(1, "bool")])]);
+
+        let type_field = |program, log_id| {
+            let logged_type = index.get(program, LogId(log_id))?;
+            Some(logged_type.application.type_decl.type_field.as_str())
+        };
+        assert_eq!(index.len(), 2);
+        assert_eq!(type_field(0, 1), Some("u64"));
+        assert_eq!(type_field(1, 1), Some("bool"));
+        assert_eq!(type_field(0, 2), None);
+        assert_eq!(type_field(2, 1), None);
+
+        assert_eq!(
+            LoggedTypeIndex::new(&abi(vec![(1, "u64")])).get(0, LogId(1)),
+            index.get(0, LogId(1))
+        );
+    }
+
+    #[test]
+    fn can_be_shared_between_threads() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<LoggedTypeIndex>();
+    }
+}
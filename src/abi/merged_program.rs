@@ -0,0 +1,416 @@
+//! Merging of several [`ProgramABI`]s into a single view sharing one set of types,
+//! e.g. to build common bindings for a workspace of contracts.
+
+use std::collections::HashMap;
+
+use crate::{
+    abi::program::{
+        ABIFunction, ConcreteTypeId, MetadataTypeId, ProgramABI, TypeApplication,
+        TypeConcreteDeclaration, TypeId, TypeMetadataDeclaration,
+    },
+    error::{error, Result},
+};
+
+/// Several ABIs merged into one, see [`MergedAbi::from_abis`].
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct MergedAbi {
+    /// The concrete types of all ABIs. Each concrete type id appears once.
+    pub concrete_types: Vec<TypeConcreteDeclaration>,
+    /// The metadata types of all ABIs, renumbered so that their ids don't collide. The
+    /// metadata types behind concrete types shared by several ABIs appear once.
+    pub metadata_types: Vec<TypeMetadataDeclaration>,
+    /// The functions of each ABI, in the order the ABIs were given.
+    pub functions: Vec<Vec<ABIFunction>>,
+}
+
+impl MergedAbi {
+    /// Merges `abis` into a single set of types.
+    ///
+    /// Concrete types are identified by their id. When several ABIs declare the same
+    /// concrete type, their metadata types are matched up and kept once. Every other
+    /// metadata type is given a new id. Declaring the same concrete type with different
+    /// shapes is an error.
+    pub fn from_abis(abis: &[ProgramABI]) -> Result<MergedAbi> {
+        let mut merged = MergedAbi::default();
+        let mut concrete_positions: HashMap<ConcreteTypeId, usize> = HashMap::new();
+
+        for abi in abis {
+            let mut ids = MetadataIds {
+                declarations: abi
+                    .metadata_types
+                    .iter()
+                    .map(|ttype| (ttype.metadata_type_id.0, ttype))
+                    .collect(),
+                merged_ids: HashMap::new(),
+            };
+
+            for concrete_type in &abi.concrete_types {
+                let (Some(local_id), Some(merged_id)) = (
+                    &concrete_type.metadata_type_id,
+                    concrete_positions
+                        .get(&concrete_type.concrete_type_id)
+                        .and_then(|&position| {
+                            merged.concrete_types[position].metadata_type_id.as_ref()
+                        }),
+                ) else {
+                    continue;
+                };
+                ids.unify(local_id.0, merged_id.0, &merged.metadata_types)?;
+            }
+
+            let fresh_types: Vec<_> = abi
+                .metadata_types
+                .iter()
+                .filter(|ttype| !ids.merged_ids.contains_key(&ttype.metadata_type_id.0))
+                .collect();
+            for (offset, ttype) in fresh_types.iter().enumerate() {
+                ids.merged_ids.insert(
+                    ttype.metadata_type_id.0,
+                    merged.metadata_types.len() + offset,
+                );
+            }
+            for ttype in fresh_types {
+                let ttype = ids.remap_declaration(ttype)?;
+                merged.metadata_types.push(ttype);
+            }
+
+            for concrete_type in &abi.concrete_types {
+                let mut concrete_type = concrete_type.clone();
+                if let Some(metadata_type_id) = &mut concrete_type.metadata_type_id {
+                    *metadata_type_id = ids.merged_id(metadata_type_id)?;
+                }
+
+                match concrete_positions.get(&concrete_type.concrete_type_id) {
+                    Some(&position) if merged.concrete_types[position] != concrete_type => {
+                        return Err(error!(
+                            "concrete type id '{}' is declared differently by several ABIs",
+                            concrete_type.concrete_type_id
+                        ));
+                    }
+                    Some(_) => {}
+                    None => {
+                        concrete_positions.insert(
+                            concrete_type.concrete_type_id.clone(),
+                            merged.concrete_types.len(),
+                        );
+                        merged.concrete_types.push(concrete_type);
+                    }
+                }
+            }
+
+            merged.functions.push(abi.functions.clone());
+        }
+
+        Ok(merged)
+    }
+}
+
+/// Maps the metadata type ids of one ABI to those of the merged ABI.
+struct MetadataIds<'a> {
+    declarations: HashMap<usize, &'a TypeMetadataDeclaration>,
+    merged_ids: HashMap<usize, usize>,
+}
+
+impl<'a> MetadataIds<'a> {
+    fn declaration(&self, id: usize) -> Result<&'a TypeMetadataDeclaration> {
+        self.declarations
+            .get(&id)
+            .copied()
+            .ok_or_else(|| error!("metadata type id '{id}' is not declared"))
+    }
+
+    fn merged_id(&self, id: &MetadataTypeId) -> Result<MetadataTypeId> {
+        self.merged_ids
+            .get(&id.0)
+            .map(|&merged_id| MetadataTypeId(merged_id))
+            .ok_or_else(|| error!("metadata type id '{id}' is not declared"))
+    }
+
+    /// Records that the local type `local_id` is the merged type `merged_id`, along with
+    /// the types they refer to. Fails if their shapes differ.
+    fn unify(
+        &mut self,
+        local_id: usize,
+        merged_id: usize,
+        merged_types: &[TypeMetadataDeclaration],
+    ) -> Result<()> {
+        let local = self.declaration(local_id)?;
+        let conflict = || {
+            error!(
+                "metadata type '{}' is declared differently by several ABIs",
+                local.type_field
+            )
+        };
+
+        if let Some(&existing) = self.merged_ids.get(&local_id) {
+            return if existing == merged_id {
+                Ok(())
+            } else {
+                Err(conflict())
+            };
+        }
+        self.merged_ids.insert(local_id, merged_id);
+
+        let merged = merged_types.get(merged_id).ok_or_else(conflict)?;
+        let local_parameters = local.type_parameters.as_deref().unwrap_or_default();
+        let merged_parameters = merged.type_parameters.as_deref().unwrap_or_default();
+        let local_components = local.components.as_deref().unwrap_or_default();
+        let merged_components = merged.components.as_deref().unwrap_or_default();
+        if local.type_field != merged.type_field
+            || local_parameters.len() != merged_parameters.len()
+            || local_components.len() != merged_components.len()
+        {
+            return Err(conflict());
+        }
+
+        for (local_parameter, merged_parameter) in local_parameters.iter().zip(merged_parameters) {
+            self.unify(local_parameter.0, merged_parameter.0, merged_types)?;
+        }
+        for (local_component, merged_component) in local_components.iter().zip(merged_components) {
+            if !self.unify_application(local_component, merged_component, merged_types)? {
+                return Err(conflict());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Like [`MetadataIds::unify`] but for type applications. Returns `false` if the
+    /// applications themselves differ.
+    fn unify_application(
+        &mut self,
+        local: &TypeApplication,
+        merged: &TypeApplication,
+        merged_types: &[TypeMetadataDeclaration],
+    ) -> Result<bool> {
+        let local_arguments = local.type_arguments.as_deref().unwrap_or_default();
+        let merged_arguments = merged.type_arguments.as_deref().unwrap_or_default();
+        if local.name != merged.name || local_arguments.len() != merged_arguments.len() {
+            return Ok(false);
+        }
+
+        match (&local.type_id, &merged.type_id) {
+            (TypeId::Concrete(local_id), TypeId::Concrete(merged_id)) if local_id == merged_id => {}
+            (TypeId::Metadata(local_id), TypeId::Metadata(merged_id)) => {
+                self.unify(local_id.0, merged_id.0, merged_types)?
+            }
+            _ => return Ok(false),
+        }
+
+        for (local_argument, merged_argument) in local_arguments.iter().zip(merged_arguments) {
+            if !self.unify_application(local_argument, merged_argument, merged_types)? {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    fn remap_declaration(
+        &self,
+        ttype: &TypeMetadataDeclaration,
+    ) -> Result<TypeMetadataDeclaration> {
+        Ok(TypeMetadataDeclaration {
+            type_field: ttype.type_field.clone(),
+            metadata_type_id: self.merged_id(&ttype.metadata_type_id)?,
+            components: ttype
+                .components
+                .as_ref()
+                .map(|components| {
+                    components
+                        .iter()
+                        .map(|component| self.remap_application(component))
+                        .collect()
+                })
+                .transpose()?,
+            type_parameters: ttype
+                .type_parameters
+                .as_ref()
+                .map(|parameters| {
+                    parameters
+                        .iter()
+                        .map(|parameter| self.merged_id(parameter))
+                        .collect()
+                })
+                .transpose()?,
+        })
+    }
+
+    fn remap_application(&self, application: &TypeApplication) -> Result<TypeApplication> {
+        Ok(TypeApplication {
+            name: application.name.clone(),
+            type_id: match &application.type_id {
+                TypeId::Concrete(concrete_type_id) => TypeId::Concrete(concrete_type_id.clone()),
+                TypeId::Metadata(metadata_type_id) => {
+                    TypeId::Metadata(self.merged_id(metadata_type_id)?)
+                }
+            },
+            type_arguments: application
+                .type_arguments
+                .as_ref()
+                .map(|arguments| {
+                    arguments
+                        .iter()
+                        .map(|argument| self.remap_application(argument))
+                        .collect()
+                })
+                .transpose()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn concrete(
+        type_field: &str,
+        id: &str,
+        metadata_type_id: Option<usize>,
+    ) -> TypeConcreteDeclaration {
+        TypeConcreteDeclaration {
+            type_field: type_field.to_string(),
+            concrete_type_id: ConcreteTypeId::from(id),
+            metadata_type_id: metadata_type_id.map(MetadataTypeId),
+            type_arguments: None,
+        }
+    }
+
+    fn metadata(
+        type_field: &str,
+        id: usize,
+        components: Vec<TypeApplication>,
+        type_parameters: Vec<usize>,
+    ) -> TypeMetadataDeclaration {
+        TypeMetadataDeclaration {
+            type_field: type_field.to_string(),
+            metadata_type_id: MetadataTypeId(id),
+            components: Some(components).filter(|components| !components.is_empty()),
+            type_parameters: Some(type_parameters.into_iter().map(MetadataTypeId).collect())
+                .filter(|parameters: &Vec<_>| !parameters.is_empty()),
+        }
+    }
+
+    fn component(name: &str, type_id: TypeId) -> TypeApplication {
+        TypeApplication {
+            name: name.to_string(),
+            type_id,
+            type_arguments: None,
+        }
+    }
+
+    fn function(name: &str, output: &str) -> ABIFunction {
+        ABIFunction {
+            name: name.to_string(),
+            output: ConcreteTypeId::from(output),
+            ..Default::default()
+        }
+    }
+
+    /// An ABI using `Point` and `Option<u64>`, with its metadata ids starting at `first_id`.
+    fn abi(function_name: &str, first_id: usize) -> ProgramABI {
+        let (point, option, generic) = (first_id, first_id + 1, first_id + 2);
+        let mut option_u64 = concrete("enum std::option::Option", "option_u64", Some(option));
+        option_u64.type_arguments = Some(vec![ConcreteTypeId::from("u64")]);
+
+        ProgramABI {
+            concrete_types: vec![
+                concrete("u64", "u64", None),
+                concrete("struct Point", "point", Some(point)),
+                option_u64,
+            ],
+            metadata_types: vec![
+                metadata(
+                    "struct Point",
+                    point,
+                    vec![component(
+                        "x",
+                        TypeId::Concrete(ConcreteTypeId::from("u64")),
+                    )],
+                    vec![],
+                ),
+                metadata(
+                    "enum std::option::Option",
+                    option,
+                    vec![
+                        component("None", TypeId::Concrete(ConcreteTypeId::from("()"))),
+                        component("Some", TypeId::Metadata(MetadataTypeId(generic))),
+                    ],
+                    vec![generic],
+                ),
+                metadata("generic T", generic, vec![], vec![]),
+            ],
+            functions: vec![function(function_name, "option_u64")],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn shared_types_are_kept_once() {
+        let mut other = abi("other_fn", 10);
+        other
+            .concrete_types
+            .push(concrete("struct Extra", "extra", Some(0)));
+        other
+            .metadata_types
+            .push(metadata("struct Extra", 0, vec![], vec![]));
+
+        let merged = MergedAbi::from_abis(&[abi("some_fn", 0), other]).unwrap();
+
+        assert_eq!(
+            merged
+                .concrete_types
+                .iter()
+                .map(|ttype| ttype.concrete_type_id.0.as_str())
+                .collect::<Vec<_>>(),
+            ["u64", "point", "option_u64", "extra"]
+        );
+        assert_eq!(
+            merged.metadata_types,
+            [
+                abi("", 0).metadata_types,
+                vec![metadata("struct Extra", 3, vec![], vec![])]
+            ]
+            .concat()
+        );
+        assert_eq!(
+            merged.concrete_types[3].metadata_type_id,
+            Some(MetadataTypeId(3))
+        );
+        assert_eq!(
+            merged.functions,
+            [
+                vec![function("some_fn", "option_u64")],
+                vec![function("other_fn", "option_u64")]
+            ]
+        );
+    }
+
+    #[test]
+    fn conflicting_concrete_types_are_rejected() {
+        let mut other = abi("other_fn", 0);
+        other.concrete_types[0].type_field = "u32".to_string();
+
+        let err =
+            MergedAbi::from_abis(&[abi("some_fn", 0), other]).expect_err("should have failed");
+
+        assert_eq!(
+            err.to_string(),
+            "concrete type id 'u64' is declared differently by several ABIs"
+        );
+    }
+
+    #[test]
+    fn conflicting_metadata_types_are_rejected() {
+        let mut other = abi("other_fn", 0);
+        other.metadata_types[0].components.as_mut().unwrap()[0].name = "y".to_string();
+
+        let err =
+            MergedAbi::from_abis(&[abi("some_fn", 0), other]).expect_err("should have failed");
+
+        assert_eq!(
+            err.to_string(),
+            "metadata type 'struct Point' is declared differently by several ABIs"
+        );
+    }
+}
@@ -1,3 +1,8 @@
+pub mod diff;
 pub mod full_program;
+pub mod log_index;
+pub mod merged_program;
 pub mod program;
+pub mod shared;
 pub mod unified_program;
+pub mod validation;
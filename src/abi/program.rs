@@ -1,7 +1,17 @@
 //! Defines a set of serializable types required for the Fuel VM ABI.
 
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt::{Display, Formatter},
+};
+
 use serde::{Deserialize, Serialize};
 
+use crate::{
+    error::{error, Error, Result},
+    utils::TypePath,
+};
+
 /// FuelVM ABI representation in JSON, originally specified
 /// [here](https://github.com/FuelLabs/fuel-specs/blob/master/specs/protocol/abi.md).
 ///
@@ -12,6 +22,9 @@ use serde::{Deserialize, Serialize};
 pub struct ProgramABI {
     pub program_type: String,
     pub spec_version: Version,
+    /// ABIs emitted before the encoding version was introduced don't have this field.
+    /// It defaults to an empty [`Version`] for them.
+    #[serde(default)]
     pub encoding_version: Version,
     pub concrete_types: Vec<TypeConcreteDeclaration>,
     pub metadata_types: Vec<TypeMetadataDeclaration>,
@@ -19,6 +32,318 @@ pub struct ProgramABI {
     pub logged_types: Option<Vec<LoggedType>>,
     pub messages_types: Option<Vec<MessageType>>,
     pub configurables: Option<Vec<Configurable>>,
+    /// The contract's storage layout, only emitted by newer compilers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage_slots: Option<Vec<StorageSlot>>,
+}
+
+impl ProgramABI {
+    /// Creates an ABI without any types, functions or other items.
+    ///
+    /// Prefer this over [`ProgramABI::default`], which leaves the program type and
+    /// versions empty and so doesn't describe a valid ABI.
+    pub fn empty(
+        program_type: ProgramType,
+        spec_version: Version,
+        encoding_version: Version,
+    ) -> Self {
+        ProgramABI {
+            program_type: program_type.as_str().to_string(),
+            spec_version,
+            encoding_version,
+            ..Default::default()
+        }
+    }
+
+    /// Parses an ABI that was already parsed as JSON, e.g. as part of a larger document.
+    pub fn from_value(abi: serde_json::Value) -> Result<Self> {
+        Ok(serde_json::from_value(abi)?)
+    }
+
+    /// Sorts the ABI's type declarations by id and its functions and configurables by
+    /// name, so that the same program always produces the same ABI regardless of the
+    /// order in which its items were emitted.
+    pub fn normalize(&mut self) {
+        self.concrete_types
+            .sort_by(|a, b| a.concrete_type_id.0.cmp(&b.concrete_type_id.0));
+        self.metadata_types
+            .sort_by_key(|metadata_type| metadata_type.metadata_type_id.0);
+        self.functions.sort_by(|a, b| a.name.cmp(&b.name));
+        if let Some(configurables) = &mut self.configurables {
+            configurables.sort_by(|a, b| a.name.cmp(&b.name));
+        }
+    }
+
+    /// Renames the struct or enum at `old_path` to `new_path` in the type field of every
+    /// concrete and metadata declaration mentioning it, including as a type argument,
+    /// e.g. `struct Wrapper<struct OldName>`. Returns the number of declarations changed.
+    pub fn rename_type(&mut self, old_path: &TypePath, new_path: &TypePath) -> Result<usize> {
+        if new_path.to_string().is_empty() {
+            return Err(error!("cannot rename type '{old_path}' to an empty path"));
+        }
+
+        let pattern = format!(
+            r"\b(struct|enum)(\s+){}([^\w:]|$)",
+            regex::escape(&old_path.to_string())
+        );
+        let regex = regex::Regex::new(&pattern).expect("the type path is escaped");
+        let replacement = format!("${{1}}${{2}}{new_path}${{3}}");
+
+        let mut renamed = 0;
+        let type_fields = self
+            .concrete_types
+            .iter_mut()
+            .map(|concrete_type| &mut concrete_type.type_field)
+            .chain(
+                self.metadata_types
+                    .iter_mut()
+                    .map(|metadata_type| &mut metadata_type.type_field),
+            );
+        for type_field in type_fields {
+            if let std::borrow::Cow::Owned(new_type_field) =
+                regex.replace_all(type_field, replacement.as_str())
+            {
+                *type_field = new_type_field;
+                renamed += 1;
+            }
+        }
+
+        Ok(renamed)
+    }
+
+    /// Indexes the concrete type declarations by id.
+    pub fn concrete_type_lookup(&self) -> HashMap<&ConcreteTypeId, &TypeConcreteDeclaration> {
+        self.concrete_types
+            .iter()
+            .map(|ttype| (&ttype.concrete_type_id, ttype))
+            .collect()
+    }
+
+    /// Indexes the metadata type declarations by id.
+    pub fn metadata_type_lookup(&self) -> HashMap<&MetadataTypeId, &TypeMetadataDeclaration> {
+        self.metadata_types
+            .iter()
+            .map(|ttype| (&ttype.metadata_type_id, ttype))
+            .collect()
+    }
+
+    /// Returns the concrete type ids used by the function called `name`: those of its
+    /// inputs and output, along with every id reachable through the `type_arguments` of
+    /// their concrete declarations. Returns `None` if there is no such function.
+    pub fn concrete_ids_for_function(&self, name: &str) -> Option<BTreeSet<ConcreteTypeId>> {
+        let function = self
+            .functions
+            .iter()
+            .find(|function| function.name == name)?;
+
+        let concrete_types = self.concrete_type_lookup();
+
+        let mut pending: Vec<&ConcreteTypeId> = function
+            .inputs
+            .iter()
+            .map(|input| &input.concrete_type_id)
+            .chain([&function.output])
+            .collect();
+        let mut concrete_ids = BTreeSet::new();
+        while let Some(concrete_type_id) = pending.pop() {
+            if !concrete_ids.insert(concrete_type_id.clone()) {
+                continue;
+            }
+            if let Some(concrete_type) = concrete_types.get(concrete_type_id) {
+                pending.extend(concrete_type.type_arguments.iter().flatten());
+            }
+        }
+
+        Some(concrete_ids)
+    }
+
+    /// Calls `f` for every type reference found in the ABI. This includes the
+    /// [`ConcreteTypeId`]s used by functions, logged types, messages, configurables and
+    /// concrete type arguments, as well as every (nested) [`TypeApplication`] found in
+    /// the metadata type components.
+    ///
+    /// The declarations themselves aren't visited: neither the ids they are declared
+    /// with, nor the `metadata_type_id` of concrete types, nor the `type_parameters` of
+    /// metadata types. Go through `concrete_types` and `metadata_types` for those.
+    pub fn visit_type_applications(&self, mut f: impl FnMut(TypeReference)) {
+        for function in &self.functions {
+            for input in &function.inputs {
+                f(TypeReference::Concrete(&input.concrete_type_id));
+            }
+            f(TypeReference::Concrete(&function.output));
+        }
+
+        for logged_type in self.logged_types.iter().flatten() {
+            f(TypeReference::Concrete(&logged_type.concrete_type_id));
+        }
+
+        for message_type in self.messages_types.iter().flatten() {
+            f(TypeReference::Concrete(&message_type.concrete_type_id));
+        }
+
+        for configurable in self.configurables.iter().flatten() {
+            f(TypeReference::Concrete(&configurable.concrete_type_id));
+        }
+
+        for storage_slot in self.storage_slots.iter().flatten() {
+            if let Some(concrete_type_id) = &storage_slot.concrete_type_id {
+                f(TypeReference::Concrete(concrete_type_id));
+            }
+        }
+
+        for concrete_type in &self.concrete_types {
+            for type_argument in concrete_type.type_arguments.iter().flatten() {
+                f(TypeReference::Concrete(type_argument));
+            }
+        }
+
+        fn visit_application(application: &TypeApplication, f: &mut impl FnMut(TypeReference)) {
+            f(TypeReference::Application(application));
+            for type_argument in application.type_arguments.iter().flatten() {
+                visit_application(type_argument, f);
+            }
+        }
+
+        for metadata_type in &self.metadata_types {
+            for component in metadata_type.components.iter().flatten() {
+                visit_application(component, &mut f);
+            }
+        }
+    }
+
+    /// Mutable counterpart of [`ProgramABI::visit_type_applications`]. Visits the same
+    /// type references in the same order, allowing `f` to modify them in place.
+    pub fn map_type_applications(&mut self, mut f: impl FnMut(TypeReferenceMut)) {
+        for function in &mut self.functions {
+            for input in &mut function.inputs {
+                f(TypeReferenceMut::Concrete(&mut input.concrete_type_id));
+            }
+            f(TypeReferenceMut::Concrete(&mut function.output));
+        }
+
+        for logged_type in self.logged_types.iter_mut().flatten() {
+            f(TypeReferenceMut::Concrete(
+                &mut logged_type.concrete_type_id,
+            ));
+        }
+
+        for message_type in self.messages_types.iter_mut().flatten() {
+            f(TypeReferenceMut::Concrete(
+                &mut message_type.concrete_type_id,
+            ));
+        }
+
+        for configurable in self.configurables.iter_mut().flatten() {
+            f(TypeReferenceMut::Concrete(
+                &mut configurable.concrete_type_id,
+            ));
+        }
+
+        for storage_slot in self.storage_slots.iter_mut().flatten() {
+            if let Some(concrete_type_id) = &mut storage_slot.concrete_type_id {
+                f(TypeReferenceMut::Concrete(concrete_type_id));
+            }
+        }
+
+        for concrete_type in &mut self.concrete_types {
+            for type_argument in concrete_type.type_arguments.iter_mut().flatten() {
+                f(TypeReferenceMut::Concrete(type_argument));
+            }
+        }
+
+        fn map_application(
+            application: &mut TypeApplication,
+            f: &mut impl FnMut(TypeReferenceMut),
+        ) {
+            f(TypeReferenceMut::Application(application));
+            for type_argument in application.type_arguments.iter_mut().flatten() {
+                map_application(type_argument, f);
+            }
+        }
+
+        for metadata_type in &mut self.metadata_types {
+            for component in metadata_type.components.iter_mut().flatten() {
+                map_application(component, &mut f);
+            }
+        }
+    }
+}
+
+/// A reference to a type found while walking a [`ProgramABI`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeReference<'a> {
+    Concrete(&'a ConcreteTypeId),
+    Application(&'a TypeApplication),
+}
+
+/// A mutable reference to a type found while walking a [`ProgramABI`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum TypeReferenceMut<'a> {
+    Concrete(&'a mut ConcreteTypeId),
+    Application(&'a mut TypeApplication),
+}
+
+/// The kind of program an ABI describes, as found in its `programType` field.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum ProgramType {
+    Script,
+    Contract,
+    Predicate,
+    Library,
+    /// A program type unknown to this crate.
+    Other(String),
+}
+
+impl ProgramType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ProgramType::Script => "script",
+            ProgramType::Contract => "contract",
+            ProgramType::Predicate => "predicate",
+            ProgramType::Library => "library",
+            ProgramType::Other(program_type) => program_type,
+        }
+    }
+}
+
+impl From<String> for ProgramType {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "script" => ProgramType::Script,
+            "contract" => ProgramType::Contract,
+            "predicate" => ProgramType::Predicate,
+            "library" => ProgramType::Library,
+            _ => ProgramType::Other(value),
+        }
+    }
+}
+
+/// Strict counterpart of the `From<String>` conversion, only accepting the program
+/// types known to this crate.
+impl TryFrom<&str> for ProgramType {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self> {
+        match ProgramType::from(value.to_string()) {
+            ProgramType::Other(program_type) => {
+                Err(error!("unknown program type '{program_type}'"))
+            }
+            program_type => Ok(program_type),
+        }
+    }
+}
+
+impl From<ProgramType> for String {
+    fn from(value: ProgramType) -> Self {
+        value.as_str().to_string()
+    }
+}
+
+impl Display for ProgramType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -30,25 +355,62 @@ impl From<&str> for Version {
     }
 }
 
+/// The ABI `specVersion` this crate parses.
+pub const SUPPORTED_SPEC_VERSION: &str = "1";
+
+/// The ABI `encodingVersion` this crate parses.
+pub const SUPPORTED_ENCODING_VERSION: &str = "1";
+
 impl Version {
+    pub fn supported_spec() -> Self {
+        SUPPORTED_SPEC_VERSION.into()
+    }
+
+    pub fn supported_encoding() -> Self {
+        SUPPORTED_ENCODING_VERSION.into()
+    }
+
     pub fn major(&self) -> Option<&str> {
-        let s = self.0.split('.').next().map(|x| x.trim());
-        match s {
-            Some("") => None,
-            s => s,
-        }
+        self.component(0)
     }
 
     pub fn minor(&self) -> Option<&str> {
-        let s = self.0.split('.').nth(1).map(|x| x.trim());
-        match s {
+        self.component(1)
+    }
+
+    pub fn patch(&self) -> Option<&str> {
+        self.component(2)
+    }
+
+    /// Parses the major, minor and patch components as numbers, a missing minor or
+    /// patch component being 0. Fails if there is no major component or if a component
+    /// isn't a number.
+    pub fn numeric_components(&self) -> Result<(u64, u64, u64)> {
+        let invalid = || Error::InvalidVersion(self.0.clone());
+        let parse = |component: &str| component.parse::<u64>().map_err(|_| invalid());
+
+        let major = parse(self.major().ok_or_else(invalid)?)?;
+        let minor = self.minor().map_or(Ok(0), parse)?;
+        let patch = self.patch().map_or(Ok(0), parse)?;
+
+        Ok((major, minor, patch))
+    }
+
+    /// Returns the `index`th numeric component of the version. A leading `v` and any
+    /// pre-release or build metadata, i.e. whatever follows a `-` or a `+`, are ignored.
+    fn component(&self, index: usize) -> Option<&str> {
+        let version = self.0.trim();
+        let version = version.strip_prefix('v').unwrap_or(version);
+        let version = version.split(['-', '+']).next().unwrap_or_default();
+
+        match version.split('.').nth(index).map(str::trim) {
             Some("") => None,
             s => s,
         }
     }
 }
 
-#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Hash)]
+#[derive(Default, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Hash)]
 pub struct ConcreteTypeId(pub String);
 
 impl From<&str> for ConcreteTypeId {
@@ -57,22 +419,116 @@ impl From<&str> for ConcreteTypeId {
     }
 }
 
-#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Hash)]
+impl ConcreteTypeId {
+    /// Creates a `ConcreteTypeId` after checking that `hex` is a 32 byte hex string.
+    /// A leading `0x` is accepted and stripped.
+    pub fn from_hex_checked(hex: &str) -> Result<Self> {
+        let digits = hex.strip_prefix("0x").unwrap_or(hex);
+
+        if digits.len() != 64 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(error!(
+                "'{hex}' is not a valid concrete type id, expected 64 hex digits"
+            ));
+        }
+
+        Ok(ConcreteTypeId(digits.to_string()))
+    }
+
+    /// Returns the id as a `0x` prefixed hex string.
+    pub fn to_prefixed_hex(&self) -> String {
+        format!("0x{}", self.0)
+    }
+}
+
+impl Display for ConcreteTypeId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Hash)]
 pub struct MetadataTypeId(pub usize);
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Hash)]
+impl Display for MetadataTypeId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A reference to either a concrete or a metadata type. In JSON, concrete type ids
+/// are strings while metadata type ids are numbers.
+///
+/// Deserialization only accepts strings of 64 hex digits as concrete type ids, so that a
+/// metadata type id mistakenly written as a string, e.g. `"123"`, is rejected rather
+/// than silently read as a concrete type id.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Hash)]
 #[serde(untagged)]
 pub enum TypeId {
     Concrete(ConcreteTypeId),
     Metadata(MetadataTypeId),
 }
 
+impl TypeId {
+    pub fn as_concrete(&self) -> Option<&ConcreteTypeId> {
+        match self {
+            TypeId::Concrete(concrete_type_id) => Some(concrete_type_id),
+            TypeId::Metadata(_) => None,
+        }
+    }
+
+    pub fn as_metadata(&self) -> Option<&MetadataTypeId> {
+        match self {
+            TypeId::Concrete(_) => None,
+            TypeId::Metadata(metadata_type_id) => Some(metadata_type_id),
+        }
+    }
+
+    pub fn is_concrete(&self) -> bool {
+        matches!(self, TypeId::Concrete(_))
+    }
+
+    pub fn is_metadata(&self) -> bool {
+        matches!(self, TypeId::Metadata(_))
+    }
+}
+
 impl Default for TypeId {
     fn default() -> Self {
         TypeId::Metadata(MetadataTypeId(usize::MAX))
     }
 }
 
+impl<'de> Deserialize<'de> for TypeId {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum RawTypeId {
+            Metadata(usize),
+            Concrete(String),
+        }
+
+        match RawTypeId::deserialize(deserializer)? {
+            RawTypeId::Metadata(id) => Ok(TypeId::Metadata(MetadataTypeId(id))),
+            RawTypeId::Concrete(id) if id.parse::<usize>().is_ok() => {
+                Err(serde::de::Error::custom(format!(
+                    "ambiguous type id '{id}', metadata type ids must be numbers"
+                )))
+            }
+            RawTypeId::Concrete(id) => {
+                if id.len() != 64 || !id.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return Err(serde::de::Error::custom(format!(
+                        "'{id}' is not a valid concrete type id, expected 64 hex digits"
+                    )));
+                }
+                Ok(TypeId::Concrete(ConcreteTypeId(id)))
+            }
+        }
+    }
+}
+
 #[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ABIFunction {
@@ -150,18 +606,137 @@ pub struct MessageType {
 pub struct Configurable {
     pub name: String,
     pub concrete_type_id: ConcreteTypeId,
+    /// Byte offset of the configurable's value in the program binary. ABIs from older
+    /// compilers may omit it, in which case it is 0 and can't be relied upon.
+    #[serde(default)]
     pub offset: u64,
     #[serde(default)]
     pub indirect: bool,
 }
 
+/// A storage slot of a contract and its initial value.
 #[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+pub struct StorageSlot {
+    /// The slot's key, as a hex string.
+    pub key: String,
+    /// The slot's initial value, as a hex string.
+    pub value: String,
+    /// The type of the value stored in the slot, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub concrete_type_id: Option<ConcreteTypeId>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Attribute {
     pub name: String,
     pub arguments: Vec<String>,
 }
 
+impl Attribute {
+    /// Interprets a `storage` attribute, e.g. `storage(read, write)`. Returns `None` for
+    /// other attributes and for `storage` attributes with no or unknown arguments.
+    pub fn as_storage(&self) -> Option<StorageAttr> {
+        if self.name != "storage" || self.arguments.is_empty() {
+            return None;
+        }
+
+        let mut storage = StorageAttr::default();
+        for argument in &self.arguments {
+            match argument.as_str() {
+                "read" => storage.read = true,
+                "write" => storage.write = true,
+                _ => return None,
+            }
+        }
+
+        Some(storage)
+    }
+
+    /// Interprets an `inline` attribute, which takes exactly one argument: `always` or
+    /// `never`. Returns `None` for other attributes and malformed `inline` attributes.
+    pub fn as_inline(&self) -> Option<InlineKind> {
+        if self.name != "inline" {
+            return None;
+        }
+
+        match self.arguments.as_slice() {
+            [arg] if arg == "always" => Some(InlineKind::Always),
+            [arg] if arg == "never" => Some(InlineKind::Never),
+            _ => None,
+        }
+    }
+}
+
+/// The arguments accepted by the `inline` attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InlineKind {
+    Always,
+    Never,
+}
+
+/// The storage access declared by a `storage` attribute.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StorageAttr {
+    pub read: bool,
+    pub write: bool,
+}
+
+macro_rules! numeric_id {
+    ($(#[$meta:meta])* $name:ident, $what:literal) => {
+        $(#[$meta])*
+        #[derive(
+            Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+        )]
+        #[serde(try_from = "String", into = "String")]
+        pub struct $name(pub u64);
+
+        impl std::str::FromStr for $name {
+            type Err = Error;
+
+            fn from_str(value: &str) -> Result<Self> {
+                value
+                    .parse()
+                    .map($name)
+                    .map_err(|_| error!("invalid {} '{}'", $what, value))
+            }
+        }
+
+        impl TryFrom<String> for $name {
+            type Error = Error;
+
+            fn try_from(value: String) -> Result<Self> {
+                value.parse()
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(value: $name) -> Self {
+                value.to_string()
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+    };
+}
+
+numeric_id!(
+    /// The id of a logged type. It is a `u64` serialized as a decimal string.
+    LogId,
+    "log id"
+);
+
+numeric_id!(
+    /// The id of a message type. It is a `u64` serialized as a decimal string.
+    MessageId,
+    "message id"
+);
+
 #[test]
 fn version_extraction_test() {
     let v = Version("1.2".to_string());
@@ -175,4 +750,490 @@ fn version_extraction_test() {
     let v = Version("".to_string());
     assert_eq!(v.major(), None);
     assert_eq!(v.minor(), None);
+
+    for version in ["v1.2.3", "1.2.3-rc.1", "1.2.3+build", "v1.2.3-rc.1+build.5"] {
+        let v = Version(version.to_string());
+        assert_eq!(v.major(), Some("1"), "{version}");
+        assert_eq!(v.minor(), Some("2"), "{version}");
+        assert_eq!(v.patch(), Some("3"), "{version}");
+    }
+
+    let v = Version("1.2-rc.1".to_string());
+    assert_eq!(v.minor(), Some("2"));
+    assert_eq!(v.patch(), None);
+}
+
+#[test]
+fn version_components_are_parsed_as_numbers() {
+    assert_eq!(
+        Version::from("v1.2.3").numeric_components().unwrap(),
+        (1, 2, 3)
+    );
+    assert_eq!(Version::from("1").numeric_components().unwrap(), (1, 0, 0));
+
+    for version in ["", "x", "1.y"] {
+        let err = Version::from(version)
+            .numeric_components()
+            .expect_err("should have failed");
+        assert!(matches!(err, Error::InvalidVersion(v) if v == version));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn program_type_is_parsed_leniently() {
+        assert_eq!(
+            ProgramType::from("contract".to_string()),
+            ProgramType::Contract
+        );
+        assert_eq!(
+            ProgramType::from("contrcat".to_string()),
+            ProgramType::Other("contrcat".to_string())
+        );
+        assert_eq!(ProgramType::Predicate.to_string(), "predicate");
+
+        let program_type: ProgramType = serde_json::from_str(r#""script""#).unwrap();
+        assert_eq!(program_type, ProgramType::Script);
+    }
+
+    #[test]
+    fn program_type_can_be_parsed_strictly() {
+        assert_eq!(
+            ProgramType::try_from("contract").unwrap(),
+            ProgramType::Contract
+        );
+
+        let err = ProgramType::try_from("bogus").expect_err("should have failed");
+
+        assert_eq!(err.to_string(), "unknown program type 'bogus'");
+    }
+
+    #[test]
+    fn supported_versions_have_a_major_component() {
+        assert_eq!(Version::supported_spec().major(), Some("1"));
+        assert_eq!(Version::supported_encoding().major(), Some("1"));
+    }
+
+    fn test_abi() -> ProgramABI {
+        let u8_id = ConcreteTypeId::from("u8");
+        let struct_id = ConcreteTypeId::from("struct_u8");
+
+        ProgramABI {
+            program_type: "contract".to_string(),
+            spec_version: "1".into(),
+            encoding_version: "1".into(),
+            concrete_types: vec![
+                TypeConcreteDeclaration {
+                    type_field: "u8".to_string(),
+                    concrete_type_id: u8_id.clone(),
+                    metadata_type_id: None,
+                    type_arguments: None,
+                },
+                TypeConcreteDeclaration {
+                    type_field: "struct SomeStruct".to_string(),
+                    concrete_type_id: struct_id.clone(),
+                    metadata_type_id: Some(MetadataTypeId(0)),
+                    type_arguments: Some(vec![u8_id.clone()]),
+                },
+            ],
+            metadata_types: vec![
+                TypeMetadataDeclaration {
+                    type_field: "struct SomeStruct".to_string(),
+                    metadata_type_id: MetadataTypeId(0),
+                    components: Some(vec![TypeApplication {
+                        name: "field".to_string(),
+                        type_id: TypeId::Metadata(MetadataTypeId(2)),
+                        type_arguments: Some(vec![TypeApplication {
+                            name: "".to_string(),
+                            type_id: TypeId::Metadata(MetadataTypeId(1)),
+                            type_arguments: None,
+                        }]),
+                    }]),
+                    type_parameters: Some(vec![MetadataTypeId(1)]),
+                },
+                TypeMetadataDeclaration {
+                    type_field: "generic T".to_string(),
+                    metadata_type_id: MetadataTypeId(1),
+                    components: None,
+                    type_parameters: None,
+                },
+            ],
+            functions: vec![ABIFunction {
+                inputs: vec![TypeConcreteParameter {
+                    name: "arg".to_string(),
+                    concrete_type_id: struct_id.clone(),
+                }],
+                name: "some_fn".to_string(),
+                output: u8_id.clone(),
+                attributes: None,
+            }],
+            logged_types: Some(vec![LoggedType {
+                log_id: "0".to_string(),
+                concrete_type_id: u8_id.clone(),
+            }]),
+            messages_types: None,
+            configurables: Some(vec![Configurable {
+                name: "SOME_CONFIGURABLE".to_string(),
+                concrete_type_id: struct_id,
+                offset: 0,
+                indirect: false,
+            }]),
+            storage_slots: None,
+        }
+    }
+
+    #[test]
+    fn storage_and_inline_attributes_are_parsed() {
+        let attribute = |name: &str, arguments: &[&str]| Attribute {
+            name: name.to_string(),
+            arguments: arguments.iter().map(|arg| arg.to_string()).collect(),
+        };
+
+        assert_eq!(
+            attribute("storage", &["read"]).as_storage(),
+            Some(StorageAttr {
+                read: true,
+                write: false
+            })
+        );
+        assert_eq!(
+            attribute("storage", &["write", "read"]).as_storage(),
+            Some(StorageAttr {
+                read: true,
+                write: true
+            })
+        );
+        assert_eq!(attribute("storage", &[]).as_storage(), None);
+        assert_eq!(attribute("storage", &["read", "delete"]).as_storage(), None);
+        assert_eq!(attribute("inline", &["never"]).as_storage(), None);
+
+        assert_eq!(
+            attribute("inline", &["always"]).as_inline(),
+            Some(InlineKind::Always)
+        );
+        assert_eq!(
+            attribute("inline", &["never"]).as_inline(),
+            Some(InlineKind::Never)
+        );
+        assert_eq!(attribute("inline", &[]).as_inline(), None);
+        assert_eq!(attribute("inline", &["always", "never"]).as_inline(), None);
+        assert_eq!(attribute("storage", &["always"]).as_inline(), None);
+    }
+
+    #[test]
+    fn empty_abi_has_its_required_fields_set() {
+        let abi = ProgramABI::empty(ProgramType::Predicate, "1".into(), "1".into());
+
+        assert_eq!(abi.program_type, "predicate");
+        assert_eq!(abi.spec_version, Version::from("1"));
+        assert!(abi.concrete_types.is_empty());
+        assert!(abi.functions.is_empty());
+        assert_eq!(abi.configurables, None);
+    }
+
+    #[test]
+    fn types_can_be_renamed() {
+        let mut abi = test_abi();
+        abi.concrete_types[0].type_field = "struct Wrapper<struct SomeStruct>".to_string();
+        abi.metadata_types[1].type_field = "struct SomeStructure".to_string();
+        let old_path = TypePath::new("SomeStruct").unwrap();
+        let new_path = TypePath::new("lib::Renamed").unwrap();
+
+        let renamed = abi.rename_type(&old_path, &new_path).unwrap();
+
+        assert_eq!(renamed, 3);
+        let type_fields = |abi: &ProgramABI| {
+            abi.concrete_types
+                .iter()
+                .map(|ttype| ttype.type_field.clone())
+                .chain(
+                    abi.metadata_types
+                        .iter()
+                        .map(|ttype| ttype.type_field.clone()),
+                )
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            type_fields(&abi),
+            [
+                "struct Wrapper<struct lib::Renamed>",
+                "struct lib::Renamed",
+                "struct lib::Renamed",
+                "struct SomeStructure",
+            ]
+        );
+        assert_eq!(abi.rename_type(&old_path, &new_path).unwrap(), 0);
+        abi.rename_type(&new_path, &TypePath::default())
+            .expect_err("should have failed");
+    }
+
+    #[test]
+    fn concrete_ids_for_function_include_type_arguments() {
+        let mut abi = test_abi();
+        // `u8` is then only reachable as the type argument of `struct_u8`.
+        abi.functions[0].output = ConcreteTypeId::from("struct_u8");
+
+        let concrete_ids = abi.concrete_ids_for_function("some_fn").unwrap();
+
+        assert_eq!(
+            concrete_ids,
+            BTreeSet::from([
+                ConcreteTypeId::from("struct_u8"),
+                ConcreteTypeId::from("u8")
+            ])
+        );
+        assert_eq!(abi.concrete_ids_for_function("missing"), None);
+    }
+
+    #[test]
+    fn concrete_type_id_hex_is_checked() {
+        let hex = "1506e6f44c1d6291cdf08395b1c4eb9a2c3d1da8cdc6e5ef6ae8308a07b85f3b";
+
+        let id = ConcreteTypeId::from_hex_checked(hex).expect("should be valid");
+        let prefixed_id =
+            ConcreteTypeId::from_hex_checked(&format!("0x{hex}")).expect("should be valid");
+
+        assert_eq!(id, prefixed_id);
+        assert_eq!(id.to_string(), hex);
+        assert_eq!(id.to_prefixed_hex(), format!("0x{hex}"));
+
+        for invalid in [
+            "",
+            "0x",
+            &hex[1..],
+            &format!("{hex}00"),
+            &hex.replace('1', "g"),
+        ] {
+            ConcreteTypeId::from_hex_checked(invalid).expect_err("should have failed");
+        }
+    }
+
+    #[test]
+    fn type_id_kind_follows_the_json_type() {
+        let hex = "1506e6f44c1d6291cdf46395a8e573276a4fa79e8ace3fc891e092ef32d1b0a0";
+
+        let concrete: TypeId = serde_json::from_str(&format!("\"{hex}\"")).unwrap();
+        assert!(concrete.is_concrete());
+        assert_eq!(concrete.as_concrete(), Some(&ConcreteTypeId::from(hex)));
+        assert_eq!(concrete.as_metadata(), None);
+
+        let metadata: TypeId = serde_json::from_str("3").unwrap();
+        assert!(metadata.is_metadata());
+        assert_eq!(metadata.as_metadata(), Some(&MetadataTypeId(3)));
+        assert_eq!(metadata.as_concrete(), None);
+    }
+
+    #[test]
+    fn ambiguous_type_ids_are_rejected() {
+        let hex = "1506e6f44c1d6291cdf46395a8e573276a4fa79e8ace3fc891e092ef32d1b0a0";
+        let parse = |json: &str| serde_json::from_str::<TypeId>(json);
+
+        assert_eq!(
+            parse(&format!("\"{hex}\"")).unwrap(),
+            TypeId::Concrete(ConcreteTypeId::from(hex))
+        );
+        assert_eq!(parse("123").unwrap(), TypeId::Metadata(MetadataTypeId(123)));
+
+        let err = parse("\"123\"").expect_err("should have failed");
+        assert!(err.to_string().contains("ambiguous type id '123'"));
+
+        let err = parse("\"u8\"").expect_err("should have failed");
+        assert!(err
+            .to_string()
+            .contains("'u8' is not a valid concrete type id"));
+    }
+
+    #[test]
+    fn type_ids_are_ordered() {
+        assert!(MetadataTypeId(2) < MetadataTypeId(10));
+        assert!(ConcreteTypeId::from("0a") < ConcreteTypeId::from("0b"));
+
+        let ids: BTreeSet<_> = [MetadataTypeId(3), MetadataTypeId(1), MetadataTypeId(2)].into();
+        assert_eq!(
+            ids.into_iter().collect::<Vec<_>>(),
+            [MetadataTypeId(1), MetadataTypeId(2), MetadataTypeId(3)]
+        );
+    }
+
+    #[test]
+    fn metadata_type_id_is_displayed_as_number() {
+        assert_eq!(MetadataTypeId(42).to_string(), "42");
+    }
+
+    #[test]
+    fn encoding_version_is_optional() {
+        let json = r#"{
+            "programType": "script",
+            "specVersion": "1",
+            "concreteTypes": [],
+            "metadataTypes": [],
+            "functions": [],
+            "loggedTypes": null,
+            "messagesTypes": null,
+            "configurables": null
+        }"#;
+
+        let abi: ProgramABI = serde_json::from_str(json).expect("should deserialize");
+
+        assert_eq!(abi.encoding_version, Version::default());
+        assert_eq!(abi.encoding_version.major(), None);
+    }
+
+    #[test]
+    fn normalize_sorts_ids_and_names() {
+        let mut abi = test_abi();
+        abi.concrete_types.reverse();
+        abi.metadata_types.reverse();
+        abi.functions.push(ABIFunction {
+            name: "another_fn".to_string(),
+            ..abi.functions[0].clone()
+        });
+        let configurables = abi.configurables.as_mut().unwrap();
+        let last_one = Configurable {
+            name: "THE_LAST_ONE".to_string(),
+            ..configurables[0].clone()
+        };
+        configurables.insert(0, last_one);
+
+        abi.normalize();
+
+        let concrete_ids: Vec<_> = abi
+            .concrete_types
+            .iter()
+            .map(|ttype| ttype.concrete_type_id.0.as_str())
+            .collect();
+        assert_eq!(concrete_ids, ["struct_u8", "u8"]);
+        let metadata_ids: Vec<_> = abi
+            .metadata_types
+            .iter()
+            .map(|ttype| ttype.metadata_type_id.0)
+            .collect();
+        assert_eq!(metadata_ids, [0, 1]);
+        let function_names: Vec<_> = abi.functions.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(function_names, ["another_fn", "some_fn"]);
+        let configurable_names: Vec<_> = abi
+            .configurables
+            .iter()
+            .flatten()
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(configurable_names, ["SOME_CONFIGURABLE", "THE_LAST_ONE"]);
+    }
+
+    #[test]
+    fn visits_every_type_application() {
+        let abi = test_abi();
+
+        let mut concrete = 0;
+        let mut applications = 0;
+        abi.visit_type_applications(|reference| match reference {
+            TypeReference::Concrete(_) => concrete += 1,
+            TypeReference::Application(_) => applications += 1,
+        });
+
+        // 2 from the function, 1 logged type, 1 configurable, 1 concrete type argument
+        assert_eq!(concrete, 5);
+        // the struct component and its nested type argument
+        assert_eq!(applications, 2);
+    }
+
+    #[test]
+    fn maps_every_type_application() {
+        let mut abi = test_abi();
+
+        abi.map_type_applications(|reference| match reference {
+            TypeReferenceMut::Concrete(id) => id.0.push_str("_renamed"),
+            TypeReferenceMut::Application(application) => application.name.push_str("_renamed"),
+        });
+
+        assert_eq!(abi.functions[0].output.0, "u8_renamed");
+        assert_eq!(
+            abi.concrete_types[1].type_arguments,
+            Some(vec![ConcreteTypeId::from("u8_renamed")])
+        );
+        let component = &abi.metadata_types[0].components.as_ref().unwrap()[0];
+        assert_eq!(component.name, "field_renamed");
+        assert_eq!(
+            component.type_arguments.as_ref().unwrap()[0].name,
+            "_renamed"
+        );
+    }
+
+    #[test]
+    fn declarations_are_not_visited() {
+        let mut abi = test_abi();
+        let declarations = (abi.concrete_types.clone(), abi.metadata_types.clone());
+
+        abi.map_type_applications(|reference| {
+            if let TypeReferenceMut::Concrete(id) = reference {
+                id.0.push_str("_renamed");
+            }
+        });
+
+        assert_eq!(abi.functions[0].output.0, "u8_renamed");
+        assert_eq!(abi.concrete_types[0], declarations.0[0]);
+        assert_eq!(
+            abi.concrete_types[1].concrete_type_id,
+            declarations.0[1].concrete_type_id
+        );
+        assert_eq!(
+            abi.concrete_types[1].metadata_type_id,
+            declarations.0[1].metadata_type_id
+        );
+        assert_eq!(abi.metadata_types, declarations.1);
+    }
+
+    #[test]
+    fn log_and_message_ids_round_trip_as_decimal_strings() {
+        let log_id: LogId = serde_json::from_str(r#""4937143""#).unwrap();
+        assert_eq!(log_id, LogId(4937143));
+        assert_eq!(serde_json::to_string(&log_id).unwrap(), r#""4937143""#);
+
+        let message_id: MessageId = serde_json::from_str(r#""0""#).unwrap();
+        assert_eq!(message_id, MessageId(0));
+        assert_eq!(serde_json::to_string(&message_id).unwrap(), r#""0""#);
+
+        assert_eq!("42".parse::<LogId>().unwrap(), LogId(42));
+        assert_eq!(MessageId(7).to_string(), "7");
+    }
+
+    #[test]
+    fn log_and_message_ids_reject_non_numeric_strings() {
+        assert!(serde_json::from_str::<LogId>(r#""abc""#).is_err());
+        assert!(serde_json::from_str::<LogId>("12").is_err());
+        assert_eq!(
+            "-1".parse::<MessageId>().unwrap_err().to_string(),
+            "invalid message id '-1'"
+        );
+    }
+
+    #[test]
+    fn configurable_offset_defaults_to_zero() {
+        let configurable: Configurable = serde_json::from_str(
+            r#"{"name": "SOME_CONFIG", "concreteTypeId": "1506e6f44c1d6291cdf46395a8e573276a4fa79e8ace3fc891e092ef32d1b0a0"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(configurable.offset, 0);
+        assert!(!configurable.indirect);
+    }
+
+    #[test]
+    fn storage_slots_survive_a_round_trip() {
+        let mut abi = test_abi();
+        let without_slots = serde_json::to_value(&abi).unwrap();
+        assert!(without_slots.get("storageSlots").is_none());
+
+        abi.storage_slots = Some(vec![StorageSlot {
+            key: "0x01".to_string(),
+            value: "0x02".to_string(),
+            concrete_type_id: Some(ConcreteTypeId::from("u8")),
+        }]);
+        let json = serde_json::to_string(&abi).unwrap();
+
+        assert_eq!(serde_json::from_str::<ProgramABI>(&json).unwrap(), abi);
+    }
 }
@@ -0,0 +1,115 @@
+//! A cheaply cloneable handle to a [`FullProgramABI`] for sharing between threads.
+
+use std::{
+    collections::HashMap,
+    ops::Deref,
+    sync::{Arc, OnceLock},
+};
+
+use crate::{
+    abi::full_program::{FullABIFunction, FullProgramABI},
+    error::Result,
+};
+
+/// A [`FullProgramABI`] behind an [`Arc`]. Clones share the ABI along with the lookup
+/// indexes computed from it, which are built on first use in a thread-safe manner.
+#[derive(Debug, Clone)]
+pub struct SharedAbi(Arc<SharedAbiInner>);
+
+#[derive(Debug)]
+struct SharedAbiInner {
+    abi: FullProgramABI,
+    functions_by_name: OnceLock<HashMap<String, usize>>,
+}
+
+impl SharedAbi {
+    pub fn new(abi: FullProgramABI) -> Self {
+        SharedAbi(Arc::new(SharedAbiInner {
+            abi,
+            functions_by_name: OnceLock::new(),
+        }))
+    }
+
+    pub fn from_json_abi(abi: &str) -> Result<Self> {
+        Ok(SharedAbi::new(FullProgramABI::from_json_abi(abi)?))
+    }
+
+    pub fn abi(&self) -> &FullProgramABI {
+        &self.0.abi
+    }
+
+    /// Same as [`FullProgramABI::get_function`], going through an index that is built
+    /// once for all clones of this handle.
+    pub fn function(&self, name: &str) -> Option<&FullABIFunction> {
+        let functions_by_name = self.0.functions_by_name.get_or_init(|| {
+            self.0
+                .abi
+                .functions
+                .iter()
+                .enumerate()
+                .map(|(position, function)| (function.name().to_string(), position))
+                .collect()
+        });
+
+        functions_by_name
+            .get(name)
+            .map(|&position| &self.0.abi.functions[position])
+    }
+}
+
+impl Deref for SharedAbi {
+    type Target = FullProgramABI;
+
+    fn deref(&self) -> &Self::Target {
+        self.abi()
+    }
+}
+
+impl From<FullProgramABI> for SharedAbi {
+    fn from(abi: FullProgramABI) -> Self {
+        SharedAbi::new(abi)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+
+    const ABI: &str = r#"{
+        "programType": "contract",
+        "specVersion": "1",
+        "encodingVersion": "1",
+        "concreteTypes": [{"type": "()", "concreteTypeId": "unit"}],
+        "metadataTypes": [],
+        "functions": [
+            {"name": "first", "inputs": [], "output": "unit", "attributes": null},
+            {"name": "second", "inputs": [], "output": "unit", "attributes": null}
+        ]
+    }"#;
+
+    #[test]
+    fn functions_are_looked_up_through_a_shared_index() {
+        let abi = SharedAbi::from_json_abi(ABI).unwrap();
+
+        let handles: Vec<_> = ["first", "second", "third"]
+            .into_iter()
+            .map(|name| {
+                let abi = abi.clone();
+                thread::spawn(move || abi.function(name).map(|f| f.name().to_string()))
+            })
+            .collect();
+        let found: Vec<_> = handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect();
+
+        assert_eq!(
+            found,
+            [Some("first".to_string()), Some("second".to_string()), None]
+        );
+        assert!(abi.0.functions_by_name.get().is_some());
+        assert_eq!(abi.functions.len(), 2);
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::{
     abi::program::{
@@ -9,11 +9,26 @@ use crate::{
 };
 
 use crate::{
-    error::{error, Result},
+    error::{error, Error, Result},
     utils::TypePath,
 };
 
-use super::program::{self, ConcreteTypeId, MessageType, TypeId, Version};
+use super::program::{
+    self, ConcreteTypeId, LogId, MessageId, MessageType, StorageSlot, TypeId, Version,
+};
+
+/// Parses a JSON ABI and unifies it, returning an error for any input that isn't a
+/// well-formed ABI.
+///
+/// Meant for ABIs coming from untrusted sources: it never panics, whatever `abi` holds.
+/// Nesting depth is bounded by the JSON parser, and concrete types referring to themselves
+/// through their type arguments are rejected. Metadata types referring to themselves
+/// through their components are only rejected when converting to a
+/// [`FullProgramABI`](crate::abi::full_program::FullProgramABI), which doesn't panic
+/// either.
+pub fn parse_and_unify(abi: &str) -> Result<UnifiedProgramABI> {
+    UnifiedProgramABI::from_json_abi(abi)
+}
 
 /// 'Unified' versions of the ABI structures removes concrete types and types metadata and unifies them under a single types declarations array.
 #[derive(Default, Debug, Clone, Eq, PartialEq)]
@@ -26,6 +41,14 @@ pub struct UnifiedProgramABI {
     pub logged_types: Option<Vec<UnifiedLoggedType>>,
     pub configurables: Option<Vec<UnifiedConfigurable>>,
     pub messages_types: Option<Vec<UnifiedMessageType>>,
+    pub storage_slots: Option<Vec<UnifiedStorageSlot>>,
+    /// The concrete type id each unified type was declared with. Only types whose
+    /// concrete id is unambiguous are present: generic types are instantiated under
+    /// several concrete ids and are left out.
+    pub concrete_ids: HashMap<usize, ConcreteTypeId>,
+    /// The concrete type ids of each function's inputs, in order, keyed by function name.
+    /// Exposed through [`UnifiedProgramABI::input_concrete_type_id`].
+    pub(crate) input_concrete_ids: HashMap<String, Vec<ConcreteTypeId>>,
 }
 
 impl UnifiedProgramABI {
@@ -34,19 +57,29 @@ impl UnifiedProgramABI {
         UnifiedProgramABI::from_counterpart(&parsed_abi)
     }
 
+    /// Like [`UnifiedProgramABI::from_json_abi`] but for an ABI that was already parsed
+    /// as JSON, e.g. as part of a larger document.
+    pub fn from_value(abi: serde_json::Value) -> Result<Self> {
+        UnifiedProgramABI::from_counterpart(&ProgramABI::from_value(abi)?)
+    }
+
     pub fn from_counterpart(program_abi: &ProgramABI) -> Result<UnifiedProgramABI> {
+        Self::ensure_unique_type_ids(program_abi)?;
+
         let mut extended_concrete_types = program_abi.concrete_types.clone();
         let mut extended_metadata_types = program_abi.metadata_types.clone();
         let mut next_metadata_type_id = extended_metadata_types
             .iter()
             .map(|v| v.metadata_type_id.0)
             .max()
-            .unwrap_or(0)
-            + 1;
+            .unwrap_or(0);
 
         // Ensure every concrete type has an associated type metadata.
         for concrete_type_decl in extended_concrete_types.iter_mut() {
             if concrete_type_decl.metadata_type_id.is_none() {
+                next_metadata_type_id = next_metadata_type_id
+                    .checked_add(1)
+                    .ok_or_else(|| error!("ran out of metadata type ids"))?;
                 extended_metadata_types.push(TypeMetadataDeclaration {
                     type_field: concrete_type_decl.type_field.clone(),
                     metadata_type_id: program::MetadataTypeId(next_metadata_type_id),
@@ -55,10 +88,23 @@ impl UnifiedProgramABI {
                 });
                 concrete_type_decl.metadata_type_id =
                     Some(program::MetadataTypeId(next_metadata_type_id));
-                next_metadata_type_id += 1;
             }
         }
 
+        let concrete_ids = Self::concrete_ids(&extended_concrete_types);
+        let input_concrete_ids = program_abi
+            .functions
+            .iter()
+            .map(|function| {
+                let ids = function
+                    .inputs
+                    .iter()
+                    .map(|input| input.concrete_type_id.clone())
+                    .collect();
+                (function.name.clone(), ids)
+            })
+            .collect();
+
         let concrete_types_lookup: HashMap<_, _> = extended_concrete_types
             .iter()
             .map(|ttype| (ttype.concrete_type_id.clone(), ttype.clone()))
@@ -67,7 +113,7 @@ impl UnifiedProgramABI {
         let types = extended_metadata_types
             .iter()
             .map(|ttype| UnifiedTypeDeclaration::from_counterpart(ttype, &concrete_types_lookup))
-            .collect();
+            .collect::<Result<_>>()?;
 
         let functions = program_abi
             .functions
@@ -82,7 +128,7 @@ impl UnifiedProgramABI {
             .map(|logged_type| {
                 UnifiedLoggedType::from_counterpart(logged_type, &concrete_types_lookup)
             })
-            .collect();
+            .collect::<Result<_>>()?;
 
         let configurables: Vec<UnifiedConfigurable> = program_abi
             .configurables
@@ -91,7 +137,7 @@ impl UnifiedProgramABI {
             .map(|configurable| {
                 UnifiedConfigurable::from_counterpart(configurable, &concrete_types_lookup)
             })
-            .collect();
+            .collect::<Result<_>>()?;
 
         let messages_types: Vec<UnifiedMessageType> = program_abi
             .messages_types
@@ -100,7 +146,20 @@ impl UnifiedProgramABI {
             .map(|message_types| {
                 UnifiedMessageType::from_counterpart(message_types, &concrete_types_lookup)
             })
-            .collect();
+            .collect::<Result<_>>()?;
+
+        let storage_slots = program_abi
+            .storage_slots
+            .as_ref()
+            .map(|storage_slots| {
+                storage_slots
+                    .iter()
+                    .map(|storage_slot| {
+                        UnifiedStorageSlot::from_counterpart(storage_slot, &concrete_types_lookup)
+                    })
+                    .collect::<Result<Vec<_>>>()
+            })
+            .transpose()?;
 
         Ok(Self {
             program_type: program_abi.program_type.clone(),
@@ -123,8 +182,105 @@ impl UnifiedProgramABI {
             } else {
                 Some(messages_types)
             },
+            storage_slots,
+            concrete_ids,
+            input_concrete_ids,
         })
     }
+
+    /// Returns the concrete type id the `index`th input of the function called `function`
+    /// was declared with in the original ABI, e.g. to refer to it when talking to a node.
+    /// Unlike [`UnifiedProgramABI::concrete_ids`], this also works for instances of
+    /// generic types.
+    pub fn input_concrete_type_id(&self, function: &str, index: usize) -> Option<&ConcreteTypeId> {
+        self.input_concrete_ids.get(function)?.get(index)
+    }
+
+    /// Indexes the type declarations by id.
+    pub fn type_lookup(&self) -> HashMap<usize, &UnifiedTypeDeclaration> {
+        self.types
+            .iter()
+            .map(|ttype| (ttype.type_id, ttype))
+            .collect()
+    }
+
+    /// Owned counterpart of [`UnifiedProgramABI::type_lookup`], as taken by the
+    /// `from_counterpart` conversions of the 'Full' types.
+    pub fn owned_type_lookup(&self) -> HashMap<usize, UnifiedTypeDeclaration> {
+        self.types
+            .iter()
+            .map(|ttype| (ttype.type_id, ttype.clone()))
+            .collect()
+    }
+
+    /// Returns the type declaration with the given `type_id`.
+    ///
+    /// This searches `types`. To look up many ids, index them once with
+    /// [`UnifiedProgramABI::type_lookup`] instead, as the conversion to
+    /// [`FullProgramABI`](crate::abi::full_program::FullProgramABI) does.
+    pub fn type_by_id(&self, type_id: usize) -> Option<&UnifiedTypeDeclaration> {
+        self.types.iter().find(|ttype| ttype.type_id == type_id)
+    }
+}
+
+impl TryFrom<&ProgramABI> for UnifiedProgramABI {
+    type Error = Error;
+
+    fn try_from(program_abi: &ProgramABI) -> Result<Self> {
+        UnifiedProgramABI::from_counterpart(program_abi)
+    }
+}
+
+impl UnifiedProgramABI {
+    fn concrete_ids(concrete_types: &[TypeConcreteDeclaration]) -> HashMap<usize, ConcreteTypeId> {
+        let mut concrete_ids = HashMap::new();
+        let mut ambiguous = HashSet::new();
+        for concrete_type in concrete_types {
+            let Some(metadata_type_id) = &concrete_type.metadata_type_id else {
+                continue;
+            };
+            let is_generic_instance = concrete_type
+                .type_arguments
+                .as_ref()
+                .is_some_and(|arguments| !arguments.is_empty());
+            if is_generic_instance
+                || concrete_ids
+                    .insert(metadata_type_id.0, concrete_type.concrete_type_id.clone())
+                    .is_some()
+            {
+                ambiguous.insert(metadata_type_id.0);
+            }
+        }
+        concrete_ids.retain(|type_id, _| !ambiguous.contains(type_id));
+
+        concrete_ids
+    }
+
+    fn ensure_unique_type_ids(program_abi: &ProgramABI) -> Result<()> {
+        let mut concrete_type_ids = HashSet::new();
+        if let Some(duplicate) = program_abi
+            .concrete_types
+            .iter()
+            .find(|ttype| !concrete_type_ids.insert(&ttype.concrete_type_id))
+        {
+            return Err(Error::DuplicateConcreteType(
+                duplicate.concrete_type_id.clone(),
+            ));
+        }
+
+        let mut metadata_type_ids = HashSet::new();
+        if let Some(duplicate) = program_abi
+            .metadata_types
+            .iter()
+            .find(|ttype| !metadata_type_ids.insert(&ttype.metadata_type_id))
+        {
+            return Err(Error::DuplicateMetadataType(
+                duplicate.metadata_type_id.clone(),
+            ));
+        }
+
+        Ok(())
+    }
 }
 
 #[derive(Default, Debug, Clone, Eq, PartialEq)]
@@ -168,7 +324,7 @@ impl UnifiedABIFunction {
                     concrete_types_lookup,
                 )
             })
-            .collect();
+            .collect::<Result<_>>()?;
 
         let attributes = abi_function
             .attributes
@@ -181,7 +337,7 @@ impl UnifiedABIFunction {
                 "".to_string(),
                 abi_function.output.clone(),
                 concrete_types_lookup,
-            ),
+            )?,
             attributes,
         )
     }
@@ -195,20 +351,49 @@ pub struct UnifiedTypeDeclaration {
     pub type_parameters: Option<Vec<usize>>,
 }
 
+#[derive(Default, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct UnifiedStorageSlot {
+    pub key: String,
+    pub value: String,
+    pub application: Option<UnifiedTypeApplication>,
+}
+
+impl UnifiedStorageSlot {
+    pub fn from_counterpart(
+        storage_slot: &StorageSlot,
+        concrete_types_lookup: &HashMap<ConcreteTypeId, TypeConcreteDeclaration>,
+    ) -> Result<UnifiedStorageSlot> {
+        Ok(UnifiedStorageSlot {
+            key: storage_slot.key.clone(),
+            value: storage_slot.value.clone(),
+            application: storage_slot
+                .concrete_type_id
+                .clone()
+                .map(|concrete_type_id| {
+                    UnifiedTypeApplication::from_concrete_type_id(
+                        "".to_string(),
+                        concrete_type_id,
+                        concrete_types_lookup,
+                    )
+                })
+                .transpose()?,
+        })
+    }
+}
+
 impl UnifiedTypeDeclaration {
     pub fn from_counterpart(
         type_decl: &TypeMetadataDeclaration,
         concrete_types_lookup: &HashMap<ConcreteTypeId, TypeConcreteDeclaration>,
-    ) -> UnifiedTypeDeclaration {
+    ) -> Result<UnifiedTypeDeclaration> {
         let components: Vec<UnifiedTypeApplication> = type_decl
             .components
-            .clone()
-            .unwrap_or_default()
-            .into_iter()
+            .iter()
+            .flatten()
             .map(|application| {
-                UnifiedTypeApplication::from_counterpart(&application, concrete_types_lookup)
+                UnifiedTypeApplication::from_counterpart(application, concrete_types_lookup)
             })
-            .collect();
+            .collect::<Result<_>>()?;
         let type_parameters: Vec<usize> = type_decl
             .type_parameters
             .clone()
@@ -216,7 +401,7 @@ impl UnifiedTypeDeclaration {
             .into_iter()
             .map(|id| id.0)
             .collect();
-        UnifiedTypeDeclaration {
+        Ok(UnifiedTypeDeclaration {
             type_id: type_decl.metadata_type_id.0,
             type_field: type_decl.type_field.clone(),
             components: if components.is_empty() {
@@ -229,7 +414,7 @@ impl UnifiedTypeDeclaration {
             } else {
                 Some(type_parameters)
             },
-        }
+        })
     }
 
     pub fn custom_type_path(&self) -> Result<TypePath> {
@@ -252,85 +437,87 @@ impl UnifiedTypeApplication {
     pub fn from_counterpart(
         type_application: &TypeApplication,
         concrete_types_lookup: &HashMap<ConcreteTypeId, TypeConcreteDeclaration>,
-    ) -> UnifiedTypeApplication {
-        let (metadata_type_id, type_arguments) = match type_application.type_id.clone() {
-            TypeId::Concrete(concrete_type_id) => (
-                concrete_types_lookup
-                    .get(&concrete_type_id)
-                    .unwrap()
-                    .metadata_type_id
-                    .clone()
-                    .unwrap(),
-                concrete_types_lookup
-                    .get(&concrete_type_id)
-                    .unwrap()
-                    .type_arguments
-                    .clone()
-                    .unwrap_or_default()
-                    .into_iter()
-                    .map(|concrete_type_id| {
-                        UnifiedTypeApplication::from_concrete_type_id(
-                            "".to_string(),
-                            concrete_type_id,
-                            concrete_types_lookup,
-                        )
-                    })
-                    .collect::<Vec<UnifiedTypeApplication>>(),
-            ),
+    ) -> Result<UnifiedTypeApplication> {
+        let (metadata_type_id, type_arguments) = match &type_application.type_id {
+            TypeId::Concrete(concrete_type_id) => {
+                let resolved = UnifiedTypeApplication::from_concrete_type_id(
+                    "".to_string(),
+                    concrete_type_id.clone(),
+                    concrete_types_lookup,
+                )?;
+                (
+                    resolved.type_id,
+                    resolved.type_arguments.unwrap_or_default(),
+                )
+            }
             TypeId::Metadata(metadata_type_id) => (
-                metadata_type_id,
+                metadata_type_id.0,
                 type_application
                     .type_arguments
-                    .clone()
-                    .unwrap_or_default()
-                    .into_iter()
+                    .iter()
+                    .flatten()
                     .map(|application| {
-                        UnifiedTypeApplication::from_counterpart(
-                            &application,
-                            concrete_types_lookup,
-                        )
+                        UnifiedTypeApplication::from_counterpart(application, concrete_types_lookup)
                     })
-                    .collect(),
+                    .collect::<Result<Vec<_>>>()?,
             ),
         };
 
-        UnifiedTypeApplication {
+        Ok(UnifiedTypeApplication {
             name: type_application.name.clone(),
-            type_id: metadata_type_id.0,
+            type_id: metadata_type_id,
             type_arguments: if type_arguments.is_empty() {
                 None
             } else {
                 Some(type_arguments)
             },
-        }
+        })
     }
 
     pub fn from_concrete_type_id(
         name: String,
         concrete_type_id: ConcreteTypeId,
         concrete_types_lookup: &HashMap<ConcreteTypeId, TypeConcreteDeclaration>,
-    ) -> UnifiedTypeApplication {
+    ) -> Result<UnifiedTypeApplication> {
+        Self::resolve_concrete_type_id(name, &concrete_type_id, concrete_types_lookup, &mut vec![])
+    }
+
+    /// `parents` holds the concrete types whose type arguments are being resolved, so that
+    /// a concrete type referring back to itself is reported instead of recursing forever.
+    fn resolve_concrete_type_id(
+        name: String,
+        concrete_type_id: &ConcreteTypeId,
+        concrete_types_lookup: &HashMap<ConcreteTypeId, TypeConcreteDeclaration>,
+        parents: &mut Vec<ConcreteTypeId>,
+    ) -> Result<UnifiedTypeApplication> {
         let concrete_type_decl = concrete_types_lookup
-            .get(&concrete_type_id)
-            .unwrap()
-            .clone();
-        let type_arguments: Vec<UnifiedTypeApplication> = concrete_type_decl
+            .get(concrete_type_id)
+            .ok_or_else(|| Error::MissingConcreteType(concrete_type_id.clone()))?;
+        if parents.contains(concrete_type_id) {
+            return Err(Error::RecursiveType(concrete_type_decl.type_field.clone()));
+        }
+        let metadata_type_id = concrete_type_decl
+            .metadata_type_id
+            .as_ref()
+            .ok_or_else(|| error!("concrete type id '{concrete_type_id}' has no metadata type"))?;
+
+        parents.push(concrete_type_id.clone());
+        let type_arguments = concrete_type_decl
             .type_arguments
-            .clone()
-            .unwrap_or_default()
-            .into_iter()
+            .iter()
+            .flatten()
             .map(|concrete_type_id| {
-                UnifiedTypeApplication::from_concrete_type_id(
+                Self::resolve_concrete_type_id(
                     "".to_string(),
                     concrete_type_id,
                     concrete_types_lookup,
+                    parents,
                 )
             })
-            .collect();
-
-        let metadata_type_id = concrete_type_decl.metadata_type_id.unwrap();
+            .collect::<Result<Vec<_>>>()?;
+        parents.pop();
 
-        UnifiedTypeApplication {
+        Ok(UnifiedTypeApplication {
             name,
             type_id: metadata_type_id.0,
             type_arguments: if type_arguments.is_empty() {
@@ -338,13 +525,13 @@ impl UnifiedTypeApplication {
             } else {
                 Some(type_arguments)
             },
-        }
+        })
     }
 }
 
 #[derive(Default, Debug, Clone, Eq, PartialEq)]
 pub struct UnifiedLoggedType {
-    pub log_id: String,
+    pub log_id: LogId,
     pub application: UnifiedTypeApplication,
 }
 
@@ -352,15 +539,15 @@ impl UnifiedLoggedType {
     fn from_counterpart(
         logged_type: &LoggedType,
         concrete_types_lookup: &HashMap<ConcreteTypeId, TypeConcreteDeclaration>,
-    ) -> UnifiedLoggedType {
-        UnifiedLoggedType {
-            log_id: logged_type.log_id.clone(),
+    ) -> Result<UnifiedLoggedType> {
+        Ok(UnifiedLoggedType {
+            log_id: logged_type.log_id.parse()?,
             application: UnifiedTypeApplication::from_concrete_type_id(
                 "".to_string(),
                 logged_type.concrete_type_id.clone(),
                 concrete_types_lookup,
-            ),
-        }
+            )?,
+        })
     }
 }
 
@@ -376,23 +563,23 @@ impl UnifiedConfigurable {
     pub fn from_counterpart(
         configurable: &Configurable,
         concrete_types_lookup: &HashMap<ConcreteTypeId, TypeConcreteDeclaration>,
-    ) -> UnifiedConfigurable {
-        UnifiedConfigurable {
+    ) -> Result<UnifiedConfigurable> {
+        Ok(UnifiedConfigurable {
             name: configurable.name.clone(),
             application: UnifiedTypeApplication::from_concrete_type_id(
                 "".to_string(),
                 configurable.concrete_type_id.clone(),
                 concrete_types_lookup,
-            ),
+            )?,
             offset: configurable.offset,
             indirect: configurable.indirect,
-        }
+        })
     }
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct UnifiedMessageType {
-    pub message_id: String,
+    pub message_id: MessageId,
     pub application: UnifiedTypeApplication,
 }
 
@@ -400,15 +587,15 @@ impl UnifiedMessageType {
     pub fn from_counterpart(
         message_type: &MessageType,
         concrete_types_lookup: &HashMap<ConcreteTypeId, TypeConcreteDeclaration>,
-    ) -> UnifiedMessageType {
-        UnifiedMessageType {
-            message_id: message_type.message_id.clone(),
+    ) -> Result<UnifiedMessageType> {
+        Ok(UnifiedMessageType {
+            message_id: message_type.message_id.parse()?,
             application: UnifiedTypeApplication::from_concrete_type_id(
                 "".to_string(),
                 message_type.concrete_type_id.clone(),
                 concrete_types_lookup,
-            ),
-        }
+            )?,
+        })
     }
 }
 
@@ -425,3 +612,291 @@ impl UnifiedTypeDeclaration {
         self.type_field.starts_with("struct ")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abi::program::{MetadataTypeId, TypeConcreteParameter};
+
+    fn concrete_type(type_field: &str, id: &str) -> TypeConcreteDeclaration {
+        TypeConcreteDeclaration {
+            type_field: type_field.to_string(),
+            concrete_type_id: ConcreteTypeId::from(id),
+            metadata_type_id: None,
+            type_arguments: None,
+        }
+    }
+
+    fn metadata_type(type_field: &str, id: usize) -> TypeMetadataDeclaration {
+        TypeMetadataDeclaration {
+            type_field: type_field.to_string(),
+            metadata_type_id: MetadataTypeId(id),
+            components: None,
+            type_parameters: None,
+        }
+    }
+
+    #[test]
+    fn types_can_be_looked_up_by_id() {
+        let type_decl = |type_id, type_field: &str| UnifiedTypeDeclaration {
+            type_id,
+            type_field: type_field.to_string(),
+            components: None,
+            type_parameters: None,
+        };
+        let mut abi = UnifiedProgramABI {
+            types: vec![type_decl(3, "u8"), type_decl(1, "u64")],
+            ..Default::default()
+        };
+
+        assert_eq!(abi.type_by_id(1).unwrap().type_field, "u64");
+        assert_eq!(abi.type_by_id(3).unwrap().type_field, "u8");
+        assert!(abi.type_by_id(2).is_none());
+        assert_eq!(abi.type_lookup()[&3], &abi.types[0]);
+        assert_eq!(abi.owned_type_lookup()[&1], abi.types[1]);
+
+        abi.types.swap(0, 1);
+        abi.types.push(type_decl(2, "bool"));
+
+        assert_eq!(abi.type_by_id(1).unwrap().type_field, "u64");
+        assert_eq!(abi.type_by_id(2).unwrap().type_field, "bool");
+    }
+
+    #[test]
+    fn json_errors_keep_their_source() {
+        let err = UnifiedProgramABI::from_json_abi("{\n  \"programType\": 1\n}")
+            .expect_err("should have failed");
+
+        let source = std::error::Error::source(&err)
+            .and_then(|source| source.downcast_ref::<serde_json::Error>())
+            .expect("source should be a serde_json::Error");
+        assert_eq!(source.line(), 2);
+        assert_eq!(source.column(), 18);
+    }
+
+    #[test]
+    fn duplicate_concrete_type_ids_are_rejected() {
+        let program_abi = ProgramABI {
+            concrete_types: vec![concrete_type("u8", "id"), concrete_type("u64", "id")],
+            ..Default::default()
+        };
+
+        let err =
+            UnifiedProgramABI::from_counterpart(&program_abi).expect_err("should have failed");
+
+        assert_eq!(
+            err.to_string(),
+            "concrete type id 'id' is declared more than once"
+        );
+        assert!(matches!(err, Error::DuplicateConcreteType(_)));
+    }
+
+    #[test]
+    fn duplicate_metadata_type_ids_are_rejected() {
+        let program_abi = ProgramABI {
+            metadata_types: vec![metadata_type("struct A", 0), metadata_type("struct B", 0)],
+            ..Default::default()
+        };
+
+        let err =
+            UnifiedProgramABI::from_counterpart(&program_abi).expect_err("should have failed");
+
+        assert_eq!(
+            err.to_string(),
+            "metadata type id '0' is declared more than once"
+        );
+        assert!(matches!(
+            err,
+            Error::DuplicateMetadataType(MetadataTypeId(0))
+        ));
+    }
+
+    #[test]
+    fn undeclared_concrete_types_are_rejected() {
+        let program_abi = ProgramABI {
+            functions: vec![ABIFunction {
+                name: "main".to_string(),
+                output: ConcreteTypeId::from("missing"),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let err =
+            UnifiedProgramABI::from_counterpart(&program_abi).expect_err("should have failed");
+
+        assert_eq!(
+            err.to_string(),
+            "concrete type id 'missing' is not declared"
+        );
+        assert!(matches!(err, Error::MissingConcreteType(_)));
+    }
+
+    #[test]
+    fn self_referencing_concrete_types_are_rejected() {
+        let mut option = concrete_type("enum std::option::Option", "option");
+        option.type_arguments = Some(vec![ConcreteTypeId::from("option")]);
+        let program_abi = ProgramABI {
+            concrete_types: vec![option],
+            functions: vec![ABIFunction {
+                name: "main".to_string(),
+                output: ConcreteTypeId::from("option"),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let err =
+            UnifiedProgramABI::from_counterpart(&program_abi).expect_err("should have failed");
+
+        assert_eq!(
+            err.to_string(),
+            "type 'enum std::option::Option' refers to itself"
+        );
+        assert!(matches!(err, Error::RecursiveType(_)));
+    }
+
+    #[test]
+    fn running_out_of_metadata_type_ids_is_an_error() {
+        let program_abi = ProgramABI {
+            concrete_types: vec![concrete_type("u8", "u8")],
+            metadata_types: vec![metadata_type("struct A", usize::MAX)],
+            ..Default::default()
+        };
+
+        let err =
+            UnifiedProgramABI::from_counterpart(&program_abi).expect_err("should have failed");
+
+        assert_eq!(err.to_string(), "ran out of metadata type ids");
+    }
+
+    #[test]
+    fn parse_and_unify_rejects_malformed_input() {
+        for abi in [
+            "",
+            "[",
+            "{}",
+            r#"{"programType":"script","specVersion":"1","encodingVersion":"1","concreteTypes":[],"metadataTypes":[],"functions":[{"name":"main","inputs":[],"output":"00","attributes":null}]}"#,
+        ] {
+            assert!(parse_and_unify(abi).is_err(), "{abi:?} should be rejected");
+        }
+    }
+
+    #[test]
+    fn concrete_ids_are_kept_for_non_generic_types() {
+        let mut option_u64 = concrete_type("enum std::option::Option", "option_u64");
+        option_u64.metadata_type_id = Some(MetadataTypeId(0));
+        option_u64.type_arguments = Some(vec![ConcreteTypeId::from("u64")]);
+        let mut point = concrete_type("struct Point", "point");
+        point.metadata_type_id = Some(MetadataTypeId(1));
+        let program_abi = ProgramABI {
+            concrete_types: vec![concrete_type("u64", "u64"), option_u64, point],
+            metadata_types: vec![
+                metadata_type("enum std::option::Option", 0),
+                metadata_type("struct Point", 1),
+            ],
+            ..Default::default()
+        };
+
+        let abi = UnifiedProgramABI::from_counterpart(&program_abi).unwrap();
+
+        let u64_type_id = abi
+            .types
+            .iter()
+            .find(|ttype| ttype.type_field == "u64")
+            .unwrap()
+            .type_id;
+        assert_eq!(abi.concrete_ids[&u64_type_id], ConcreteTypeId::from("u64"));
+        assert_eq!(abi.concrete_ids[&1], ConcreteTypeId::from("point"));
+        assert!(!abi.concrete_ids.contains_key(&0));
+    }
+
+    #[test]
+    fn input_concrete_ids_are_recovered() {
+        let mut option_u64 = concrete_type("enum std::option::Option", "option_u64");
+        option_u64.metadata_type_id = Some(MetadataTypeId(0));
+        option_u64.type_arguments = Some(vec![ConcreteTypeId::from("u64")]);
+        let program_abi = ProgramABI {
+            concrete_types: vec![concrete_type("u64", "u64"), option_u64],
+            metadata_types: vec![metadata_type("enum std::option::Option", 0)],
+            functions: vec![ABIFunction {
+                name: "main".to_string(),
+                inputs: vec![
+                    TypeConcreteParameter {
+                        name: "amount".to_string(),
+                        concrete_type_id: ConcreteTypeId::from("u64"),
+                    },
+                    TypeConcreteParameter {
+                        name: "limit".to_string(),
+                        concrete_type_id: ConcreteTypeId::from("option_u64"),
+                    },
+                ],
+                output: ConcreteTypeId::from("u64"),
+                attributes: None,
+            }],
+            ..Default::default()
+        };
+
+        let abi = UnifiedProgramABI::from_counterpart(&program_abi).unwrap();
+
+        assert_eq!(
+            abi.input_concrete_type_id("main", 1),
+            Some(&ConcreteTypeId::from("option_u64"))
+        );
+        assert_eq!(
+            abi.input_concrete_type_id("main", 0),
+            Some(&ConcreteTypeId::from("u64"))
+        );
+        assert_eq!(abi.input_concrete_type_id("main", 2), None);
+        assert_eq!(abi.input_concrete_type_id("missing", 0), None);
+    }
+
+    #[test]
+    fn can_be_built_from_a_json_value() {
+        let value = serde_json::json!({
+            "programType": "script",
+            "specVersion": "1",
+            "encodingVersion": "1",
+            "concreteTypes": [{"type": "()", "concreteTypeId": "unit"}],
+            "metadataTypes": [],
+            "functions": [{"name": "main", "inputs": [], "output": "unit", "attributes": null}]
+        });
+
+        let abi = UnifiedProgramABI::from_value(value.clone()).unwrap();
+
+        assert_eq!(
+            abi,
+            UnifiedProgramABI::from_json_abi(&value.to_string()).unwrap()
+        );
+        assert_eq!(abi.functions[0].name, "main");
+        UnifiedProgramABI::from_value(serde_json::json!([])).expect_err("should have failed");
+    }
+
+    #[test]
+    fn storage_slots_are_unified() {
+        let program_abi = ProgramABI {
+            concrete_types: vec![concrete_type("u64", "u64")],
+            storage_slots: Some(vec![
+                StorageSlot {
+                    key: "0x01".to_string(),
+                    value: "0x02".to_string(),
+                    concrete_type_id: Some(ConcreteTypeId::from("u64")),
+                },
+                StorageSlot {
+                    key: "0x03".to_string(),
+                    value: "0x04".to_string(),
+                    concrete_type_id: None,
+                },
+            ]),
+            ..Default::default()
+        };
+
+        let abi = UnifiedProgramABI::from_counterpart(&program_abi).unwrap();
+
+        let storage_slots = abi.storage_slots.unwrap();
+        let application = storage_slots[0].application.as_ref().unwrap();
+        assert_eq!(abi.types[0].type_id, application.type_id);
+        assert_eq!(storage_slots[1].key, "0x03");
+        assert_eq!(storage_slots[1].application, None);
+    }
+}
@@ -0,0 +1,269 @@
+//! Referential integrity checks for [`ProgramABI`]s coming from untrusted sources.
+
+use std::collections::HashSet;
+
+use crate::abi::program::{
+    ConcreteTypeId, LogId, MessageId, MetadataTypeId, ProgramABI, TypeApplication, TypeId,
+    TypeReference,
+};
+
+/// A problem found by [`ProgramABI::validate`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ValidationError {
+    #[error("concrete type id '{0}' is not declared")]
+    UnknownConcreteType(ConcreteTypeId),
+    #[error("metadata type id '{0}' is not declared")]
+    UnknownMetadataType(MetadataTypeId),
+    #[error("type '{type_field}' takes {expected} type arguments but {actual} were given")]
+    TypeArgumentCount {
+        type_field: String,
+        expected: usize,
+        actual: usize,
+    },
+    #[error("invalid log id '{0}'")]
+    InvalidLogId(String),
+    #[error("invalid message id '{0}'")]
+    InvalidMessageId(String),
+    #[error("configurable '{0}' is declared more than once")]
+    DuplicateConfigurable(String),
+}
+
+impl ProgramABI {
+    /// Checks that every type the ABI refers to is declared, that generic types are
+    /// given as many type arguments as they have type parameters, and that log and
+    /// message ids are valid. Configurable names must be unique, since configurables are
+    /// set by name. All the problems found are returned, not just the first.
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let concrete_types = self.concrete_type_lookup();
+        let metadata_types = self.metadata_type_lookup();
+
+        let mut errors = vec![];
+
+        // `type_arguments` is `None` where the arity isn't checked. Omitted type arguments
+        // count as none, so a generic type used without them is reported.
+        let mut check_metadata_type = |id: &MetadataTypeId, type_arguments: Option<usize>| {
+            let Some(metadata_type) = metadata_types.get(id) else {
+                errors.push(ValidationError::UnknownMetadataType(id.clone()));
+                return;
+            };
+
+            let expected = metadata_type.type_parameters.iter().flatten().count();
+            if let Some(actual) = type_arguments.filter(|&actual| actual != expected) {
+                errors.push(ValidationError::TypeArgumentCount {
+                    type_field: metadata_type.type_field.clone(),
+                    expected,
+                    actual,
+                });
+            }
+        };
+
+        for concrete_type in &self.concrete_types {
+            if let Some(metadata_type_id) = &concrete_type.metadata_type_id {
+                check_metadata_type(
+                    metadata_type_id,
+                    Some(concrete_type.type_arguments.as_ref().map_or(0, Vec::len)),
+                );
+            }
+        }
+
+        for metadata_type in &self.metadata_types {
+            for type_parameter in metadata_type.type_parameters.iter().flatten() {
+                check_metadata_type(type_parameter, None);
+            }
+        }
+
+        let mut unknown_concrete_types = vec![];
+        self.visit_type_applications(|reference| match reference {
+            TypeReference::Concrete(id)
+            | TypeReference::Application(TypeApplication {
+                type_id: TypeId::Concrete(id),
+                ..
+            }) => {
+                if !concrete_types.contains_key(id) {
+                    unknown_concrete_types.push(ValidationError::UnknownConcreteType(id.clone()));
+                }
+            }
+            TypeReference::Application(application) => {
+                if let TypeId::Metadata(id) = &application.type_id {
+                    check_metadata_type(
+                        id,
+                        Some(application.type_arguments.as_ref().map_or(0, Vec::len)),
+                    );
+                }
+            }
+        });
+        errors.extend(unknown_concrete_types);
+
+        for logged_type in self.logged_types.iter().flatten() {
+            if logged_type.log_id.parse::<LogId>().is_err() {
+                errors.push(ValidationError::InvalidLogId(logged_type.log_id.clone()));
+            }
+        }
+
+        for message_type in self.messages_types.iter().flatten() {
+            if message_type.message_id.parse::<MessageId>().is_err() {
+                errors.push(ValidationError::InvalidMessageId(
+                    message_type.message_id.clone(),
+                ));
+            }
+        }
+
+        let mut configurable_names = HashSet::new();
+        for configurable in self.configurables.iter().flatten() {
+            if !configurable_names.insert(&configurable.name) {
+                errors.push(ValidationError::DuplicateConfigurable(
+                    configurable.name.clone(),
+                ));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abi::program::{
+        ABIFunction, Configurable, LoggedType, MessageType, TypeConcreteDeclaration,
+        TypeConcreteParameter, TypeMetadataDeclaration,
+    };
+
+    fn valid_abi() -> ProgramABI {
+        let u8_id = ConcreteTypeId::from("u8");
+        let option_id = ConcreteTypeId::from("option_u8");
+
+        ProgramABI {
+            concrete_types: vec![
+                TypeConcreteDeclaration {
+                    type_field: "u8".to_string(),
+                    concrete_type_id: u8_id.clone(),
+                    metadata_type_id: None,
+                    type_arguments: None,
+                },
+                TypeConcreteDeclaration {
+                    type_field: "enum std::option::Option".to_string(),
+                    concrete_type_id: option_id.clone(),
+                    metadata_type_id: Some(MetadataTypeId(0)),
+                    type_arguments: Some(vec![u8_id.clone()]),
+                },
+            ],
+            metadata_types: vec![
+                TypeMetadataDeclaration {
+                    type_field: "enum std::option::Option".to_string(),
+                    metadata_type_id: MetadataTypeId(0),
+                    components: Some(vec![TypeApplication {
+                        name: "Some".to_string(),
+                        type_id: TypeId::Metadata(MetadataTypeId(1)),
+                        type_arguments: None,
+                    }]),
+                    type_parameters: Some(vec![MetadataTypeId(1)]),
+                },
+                TypeMetadataDeclaration {
+                    type_field: "generic T".to_string(),
+                    metadata_type_id: MetadataTypeId(1),
+                    components: None,
+                    type_parameters: None,
+                },
+            ],
+            functions: vec![ABIFunction {
+                inputs: vec![TypeConcreteParameter {
+                    name: "arg".to_string(),
+                    concrete_type_id: option_id,
+                }],
+                name: "main".to_string(),
+                output: u8_id.clone(),
+                attributes: None,
+            }],
+            logged_types: Some(vec![LoggedType {
+                log_id: "1".to_string(),
+                concrete_type_id: u8_id.clone(),
+            }]),
+            messages_types: Some(vec![MessageType {
+                message_id: "2".to_string(),
+                concrete_type_id: u8_id,
+            }]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn valid_abi_passes() {
+        assert_eq!(valid_abi().validate(), Ok(()));
+    }
+
+    #[test]
+    fn every_problem_is_reported() {
+        let mut abi = valid_abi();
+        abi.functions[0].output = ConcreteTypeId::from("missing");
+        abi.concrete_types[1].type_arguments = Some(vec![]);
+        abi.metadata_types[0].components.as_mut().unwrap()[0].type_id =
+            TypeId::Metadata(MetadataTypeId(7));
+        abi.logged_types.as_mut().unwrap()[0].log_id = "log".to_string();
+        abi.messages_types.as_mut().unwrap()[0].message_id = "-2".to_string();
+
+        let errors = abi.validate().expect_err("should have failed");
+
+        assert_eq!(
+            errors,
+            [
+                ValidationError::TypeArgumentCount {
+                    type_field: "enum std::option::Option".to_string(),
+                    expected: 1,
+                    actual: 0,
+                },
+                ValidationError::UnknownMetadataType(MetadataTypeId(7)),
+                ValidationError::UnknownConcreteType(ConcreteTypeId::from("missing")),
+                ValidationError::InvalidLogId("log".to_string()),
+                ValidationError::InvalidMessageId("-2".to_string()),
+            ]
+        );
+        assert_eq!(
+            errors[0].to_string(),
+            "type 'enum std::option::Option' takes 1 type arguments but 0 were given"
+        );
+    }
+
+    #[test]
+    fn omitted_type_arguments_of_generic_types_are_reported() {
+        let mut abi = valid_abi();
+        abi.concrete_types[1].type_arguments = None;
+        abi.metadata_types[0].components.as_mut().unwrap()[0] = TypeApplication {
+            name: "Some".to_string(),
+            type_id: TypeId::Metadata(MetadataTypeId(0)),
+            type_arguments: None,
+        };
+
+        let errors = abi.validate().expect_err("should have failed");
+
+        let omitted = ValidationError::TypeArgumentCount {
+            type_field: "enum std::option::Option".to_string(),
+            expected: 1,
+            actual: 0,
+        };
+        assert_eq!(errors, [omitted.clone(), omitted]);
+    }
+
+    #[test]
+    fn duplicate_configurable_names_are_reported() {
+        let configurable = |offset| Configurable {
+            name: "OWNER".to_string(),
+            concrete_type_id: ConcreteTypeId::from("u8"),
+            offset,
+            indirect: false,
+        };
+        let mut abi = valid_abi();
+        abi.configurables = Some(vec![configurable(0), configurable(8)]);
+
+        let errors = abi.validate().expect_err("should have failed");
+
+        assert_eq!(
+            errors,
+            [ValidationError::DuplicateConfigurable("OWNER".to_string())]
+        );
+    }
+}
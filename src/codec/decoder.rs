@@ -0,0 +1,305 @@
+use crate::{
+    abi::full_program::{FullConfigurable, FullTypeApplication, FullTypeDeclaration},
+    codec::{is_b256_wrapper, DecodedValue},
+    error::{error, Result},
+    utils::{extract_generic_name, has_tuple_format, try_extract_array_len, try_extract_str_len},
+};
+
+impl FullTypeApplication {
+    /// Decodes a value of this type from the start of `bytes`. Any bytes following the
+    /// value are ignored.
+    pub fn decode(&self, bytes: &[u8]) -> Result<DecodedValue> {
+        let mut decoder = Decoder { bytes, offset: 0 };
+        decoder.decode(&self.monomorphized().type_decl)
+    }
+}
+
+impl FullConfigurable {
+    /// Decodes the default value of the configurable, as found at its offset in the
+    /// program's `binary`. Indirect configurables aren't supported since they only
+    /// store a pointer to their value at the offset.
+    pub fn decode_default(&self, binary: &[u8]) -> Result<DecodedValue> {
+        if self.indirect {
+            return Err(error!(
+                "decoding indirect configurable '{}' is not supported",
+                self.name
+            ));
+        }
+
+        let bytes = usize::try_from(self.offset)
+            .ok()
+            .and_then(|offset| binary.get(offset..))
+            .ok_or_else(|| {
+                error!(
+                    "configurable '{}' has offset {} beyond the binary length {}",
+                    self.name,
+                    self.offset,
+                    binary.len()
+                )
+            })?;
+
+        self.application.decode(bytes)
+    }
+}
+
+struct Decoder<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl Decoder<'_> {
+    fn decode(&mut self, type_decl: &FullTypeDeclaration) -> Result<DecodedValue> {
+        let type_field = type_decl.type_field.as_str();
+
+        let value = match type_field {
+            "()" => DecodedValue::Unit,
+            "bool" => match self.take::<1>()? {
+                [0] => DecodedValue::Bool(false),
+                [1] => DecodedValue::Bool(true),
+                [other] => return Err(error!("invalid bool value {other}")),
+            },
+            "u8" => DecodedValue::U8(u8::from_be_bytes(self.take()?)),
+            "u16" => DecodedValue::U16(u16::from_be_bytes(self.take()?)),
+            "u32" => DecodedValue::U32(u32::from_be_bytes(self.take()?)),
+            "u64" => DecodedValue::U64(u64::from_be_bytes(self.take()?)),
+            "u256" => DecodedValue::U256(self.take()?),
+            "b256" => DecodedValue::B256(self.take()?),
+            _ => return self.decode_composite(type_decl),
+        };
+
+        Ok(value)
+    }
+
+    fn decode_composite(&mut self, type_decl: &FullTypeDeclaration) -> Result<DecodedValue> {
+        let type_field = type_decl.type_field.as_str();
+
+        if type_decl.is_heap_type() {
+            return Err(error!("decoding heap type '{type_field}' is not supported"));
+        }
+
+        if let Some(name) = extract_generic_name(type_field) {
+            return Err(error!("cannot decode unbound generic '{name}'"));
+        }
+
+        if let Some(len) = try_extract_str_len(type_field)? {
+            let bytes = self.take_slice(len)?;
+            let string = std::str::from_utf8(bytes)
+                .map_err(|err| error!("invalid utf-8 in '{type_field}': {err}"))?;
+            return Ok(DecodedValue::String(string.to_string()));
+        }
+
+        if let Some(len) = try_extract_array_len(type_field)? {
+            let element = type_decl
+                .components
+                .first()
+                .ok_or_else(|| error!("array '{type_field}' has no element type"))?;
+            let elements = (0..len)
+                .map(|_| self.decode(&element.type_decl))
+                .collect::<Result<_>>()?;
+            return Ok(DecodedValue::Array(elements));
+        }
+
+        if has_tuple_format(type_field) {
+            let elements = type_decl
+                .components
+                .iter()
+                .map(|component| self.decode(&component.type_decl))
+                .collect::<Result<_>>()?;
+            return Ok(DecodedValue::Tuple(elements));
+        }
+
+        if is_b256_wrapper(type_decl) {
+            return Ok(DecodedValue::B256(self.take()?));
+        }
+
+        if type_decl.is_struct_type() {
+            let fields = type_decl
+                .components
+                .iter()
+                .map(|field| Ok((field.name.clone(), self.decode(&field.type_decl)?)))
+                .collect::<Result<_>>()?;
+            return Ok(DecodedValue::Struct(fields));
+        }
+
+        if type_decl.is_never_returning() {
+            return Err(error!("cannot decode '{type_field}', it has no values"));
+        }
+
+        if type_decl.is_enum_type() {
+            let discriminant = u64::from_be_bytes(self.take()?);
+            let variant = type_decl
+                .enum_variant_by_index(discriminant)
+                .ok_or_else(|| {
+                    error!("discriminant {discriminant} is out of range for '{type_field}'")
+                })?;
+            let value = self.decode(&variant.type_decl)?;
+            return Ok(DecodedValue::Enum {
+                variant: variant.name.clone(),
+                value: Box::new(value),
+            });
+        }
+
+        Err(error!("decoding type '{type_field}' is not supported"))
+    }
+
+    fn take<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let bytes = self.take_slice(N)?;
+        Ok(bytes.try_into().expect("slice has exactly N bytes"))
+    }
+
+    fn take_slice(&mut self, len: usize) -> Result<&[u8]> {
+        let bytes = self
+            .offset
+            .checked_add(len)
+            .and_then(|end| self.bytes.get(self.offset..end))
+            .ok_or_else(|| {
+                error!(
+                    "not enough bytes to decode: needed {len} at offset {}, but only {} are available",
+                    self.offset,
+                    self.bytes.len()
+                )
+            })?;
+        self.offset += len;
+
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{appl, decl};
+
+    #[test]
+    fn decodes_primitives() {
+        let decode =
+            |type_field: &str, bytes: &[u8]| appl("", decl(type_field, vec![])).decode(bytes);
+
+        assert_eq!(decode("()", &[]).unwrap(), DecodedValue::Unit);
+        assert_eq!(decode("bool", &[1]).unwrap(), DecodedValue::Bool(true));
+        assert_eq!(decode("u8", &[7]).unwrap(), DecodedValue::U8(7));
+        assert_eq!(decode("u16", &[1, 0]).unwrap(), DecodedValue::U16(256));
+        assert_eq!(
+            decode("u32", &[0, 0, 1, 0]).unwrap(),
+            DecodedValue::U32(256)
+        );
+        assert_eq!(
+            decode("u64", &[0, 0, 0, 0, 0, 0, 1, 0]).unwrap(),
+            DecodedValue::U64(256)
+        );
+        assert_eq!(
+            decode("str[2]", b"hi").unwrap(),
+            DecodedValue::String("hi".to_string())
+        );
+
+        decode("bool", &[2]).expect_err("should have failed");
+        decode("u64", &[0; 7]).expect_err("should have failed");
+    }
+
+    #[test]
+    fn decodes_b256_wrappers_as_b256() {
+        let b256 = |bytes: &[u8]| appl("", decl("b256", vec![])).decode(bytes);
+        assert_eq!(b256(&[7; 32]).unwrap(), DecodedValue::B256([7; 32]));
+
+        let asset_id = decl(
+            "struct std::asset_id::AssetId",
+            vec![appl("bits", decl("b256", vec![]))],
+        );
+        assert_eq!(
+            appl("", asset_id).decode(&[1; 32]).unwrap(),
+            DecodedValue::B256([1; 32])
+        );
+
+        let custom = decl("struct Wrapper", vec![appl("bits", decl("b256", vec![]))]);
+        assert_eq!(
+            appl("", custom).decode(&[1; 32]).unwrap(),
+            DecodedValue::Struct(vec![("bits".to_string(), DecodedValue::B256([1; 32]))])
+        );
+    }
+
+    #[test]
+    fn decodes_composite_types() {
+        let u8_decl = decl("u8", vec![]);
+        let a_struct = decl(
+            "struct SomeStruct",
+            vec![
+                appl("a", u8_decl.clone()),
+                appl(
+                    "b",
+                    decl("[_; 2]", vec![appl("__array_element", u8_decl.clone())]),
+                ),
+                appl(
+                    "c",
+                    decl(
+                        "enum SomeEnum",
+                        vec![appl("A", decl("()", vec![])), appl("B", u8_decl.clone())],
+                    ),
+                ),
+                appl(
+                    "d",
+                    decl(
+                        "(_, _)",
+                        vec![
+                            appl("__tuple_element", u8_decl.clone()),
+                            appl("__tuple_element", decl("bool", vec![])),
+                        ],
+                    ),
+                ),
+            ],
+        );
+
+        let bytes = [1, 2, 3, 0, 0, 0, 0, 0, 0, 0, 1, 4, 5, 0];
+        let decoded = appl("", a_struct).decode(&bytes).unwrap();
+
+        assert_eq!(
+            decoded,
+            DecodedValue::Struct(vec![
+                ("a".to_string(), DecodedValue::U8(1)),
+                (
+                    "b".to_string(),
+                    DecodedValue::Array(vec![DecodedValue::U8(2), DecodedValue::U8(3)])
+                ),
+                (
+                    "c".to_string(),
+                    DecodedValue::Enum {
+                        variant: "B".to_string(),
+                        value: Box::new(DecodedValue::U8(4)),
+                    }
+                ),
+                (
+                    "d".to_string(),
+                    DecodedValue::Tuple(vec![DecodedValue::U8(5), DecodedValue::Bool(false)])
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn decodes_configurable_default_at_its_offset() {
+        let configurable = FullConfigurable {
+            name: "SOME_CONFIGURABLE".to_string(),
+            application: appl("", decl("u16", vec![])),
+            offset: 2,
+            indirect: false,
+        };
+
+        let decoded = configurable.decode_default(&[9, 9, 0, 42]).unwrap();
+        assert_eq!(decoded, DecodedValue::U16(42));
+
+        configurable
+            .decode_default(&[9, 9, 0])
+            .expect_err("should have failed");
+    }
+
+    #[test]
+    fn heap_types_are_not_supported() {
+        let vec = appl("", decl("struct std::vec::Vec", vec![]));
+
+        let err = vec.decode(&[0; 64]).expect_err("should have failed");
+
+        assert_eq!(
+            err.to_string(),
+            "decoding heap type 'struct std::vec::Vec' is not supported"
+        );
+    }
+}
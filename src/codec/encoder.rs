@@ -0,0 +1,389 @@
+use std::collections::HashMap;
+
+use crate::{
+    abi::full_program::{FullProgramABI, FullTypeApplication, FullTypeDeclaration},
+    codec::{is_b256_wrapper, DecodedValue},
+    error::{error, Result},
+    utils::{extract_generic_name, has_tuple_format, try_extract_array_len, try_extract_str_len},
+};
+
+impl FullTypeApplication {
+    /// Encodes `value` as a value of this type.
+    pub fn encode(&self, value: &DecodedValue) -> Result<Vec<u8>> {
+        let mut bytes = vec![];
+        encode(&self.monomorphized().type_decl, value, &mut bytes)?;
+
+        Ok(bytes)
+    }
+}
+
+impl FullProgramABI {
+    /// Encodes the configurables set in `values`, keyed by configurable name, returning
+    /// each encoded value along with the offset at which it goes in the program binary.
+    /// Configurables missing from `values` keep their default value and are skipped.
+    /// Entries are ordered as the configurables in the ABI. Indirect configurables
+    /// aren't supported since only a pointer to their value is stored at the offset.
+    pub fn encode_configurables(
+        &self,
+        values: &HashMap<String, DecodedValue>,
+    ) -> Result<Vec<(u64, Vec<u8>)>> {
+        if let Some(unknown) = values
+            .keys()
+            .find(|name| !self.configurables.iter().any(|c| &c.name == *name))
+        {
+            return Err(error!("the ABI has no configurable named '{unknown}'"));
+        }
+
+        self.configurables
+            .iter()
+            .filter_map(|configurable| {
+                let value = values.get(&configurable.name)?;
+                if configurable.indirect {
+                    return Some(Err(error!(
+                        "encoding indirect configurable '{}' is not supported",
+                        configurable.name
+                    )));
+                }
+                let encoded = configurable.application.encode(value).map_err(|err| {
+                    error!("cannot encode configurable '{}':", configurable.name).combine(err)
+                });
+                Some(encoded.map(|bytes| (configurable.offset, bytes)))
+            })
+            .collect()
+    }
+}
+
+fn encode(
+    type_decl: &FullTypeDeclaration,
+    value: &DecodedValue,
+    bytes: &mut Vec<u8>,
+) -> Result<()> {
+    let type_field = type_decl.type_field.as_str();
+
+    match (type_field, value) {
+        ("()", DecodedValue::Unit) => {}
+        ("bool", DecodedValue::Bool(value)) => bytes.push(u8::from(*value)),
+        ("u8", DecodedValue::U8(value)) => bytes.push(*value),
+        ("u16", DecodedValue::U16(value)) => bytes.extend(value.to_be_bytes()),
+        ("u32", DecodedValue::U32(value)) => bytes.extend(value.to_be_bytes()),
+        ("u64", DecodedValue::U64(value)) => bytes.extend(value.to_be_bytes()),
+        ("u256", DecodedValue::U256(value)) => bytes.extend(value),
+        ("b256", DecodedValue::B256(value)) => bytes.extend(value),
+        _ => encode_composite(type_decl, value, bytes)?,
+    }
+
+    Ok(())
+}
+
+fn encode_composite(
+    type_decl: &FullTypeDeclaration,
+    value: &DecodedValue,
+    bytes: &mut Vec<u8>,
+) -> Result<()> {
+    let type_field = type_decl.type_field.as_str();
+    let mismatch = || error!("cannot encode {value:?} as '{type_field}'");
+
+    if type_decl.is_heap_type() {
+        return Err(error!("encoding heap type '{type_field}' is not supported"));
+    }
+
+    if let Some(name) = extract_generic_name(type_field) {
+        return Err(error!("cannot encode unbound generic '{name}'"));
+    }
+
+    if let Some(len) = try_extract_str_len(type_field)? {
+        return match value {
+            DecodedValue::String(string) if string.len() == len => {
+                bytes.extend(string.as_bytes());
+                Ok(())
+            }
+            _ => Err(mismatch()),
+        };
+    }
+
+    if let Some(len) = try_extract_array_len(type_field)? {
+        let element = type_decl
+            .components
+            .first()
+            .ok_or_else(|| error!("array '{type_field}' has no element type"))?;
+        return match value {
+            DecodedValue::Array(elements) if elements.len() == len => elements
+                .iter()
+                .try_for_each(|element_value| encode(&element.type_decl, element_value, bytes)),
+            _ => Err(mismatch()),
+        };
+    }
+
+    if has_tuple_format(type_field) {
+        return match value {
+            DecodedValue::Tuple(elements) if elements.len() == type_decl.components.len() => {
+                type_decl
+                    .components
+                    .iter()
+                    .zip(elements)
+                    .try_for_each(|(component, element)| {
+                        encode(&component.type_decl, element, bytes)
+                    })
+            }
+            _ => Err(mismatch()),
+        };
+    }
+
+    if let DecodedValue::B256(value) = value {
+        if is_b256_wrapper(type_decl) {
+            bytes.extend(value);
+            return Ok(());
+        }
+    }
+
+    if type_decl.is_struct_type() {
+        return match value {
+            DecodedValue::Struct(fields)
+                if fields.len() == type_decl.components.len()
+                    && type_decl
+                        .components
+                        .iter()
+                        .zip(fields)
+                        .all(|(component, (name, _))| &component.name == name) =>
+            {
+                type_decl
+                    .components
+                    .iter()
+                    .zip(fields)
+                    .try_for_each(|(component, (_, field))| {
+                        encode(&component.type_decl, field, bytes)
+                    })
+            }
+            _ => Err(mismatch()),
+        };
+    }
+
+    if type_decl.is_enum_type() {
+        let DecodedValue::Enum { variant, value } = value else {
+            return Err(mismatch());
+        };
+        let discriminant = type_decl
+            .enum_variant_index(variant)
+            .ok_or_else(|| error!("'{type_field}' has no variant named '{variant}'"))?;
+        let variant_type = type_decl
+            .enum_variant_by_index(discriminant)
+            .expect("variant index was just looked up");
+
+        bytes.extend(discriminant.to_be_bytes());
+        return encode(&variant_type.type_decl, value, bytes);
+    }
+
+    Err(mismatch())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        abi::full_program::FullConfigurable,
+        test_utils::{appl, decl, full_abi},
+    };
+
+    fn assert_round_trips(application: &FullTypeApplication, value: DecodedValue) {
+        let bytes = application.encode(&value).expect("should encode");
+
+        if let Some(size) = application.encoded_size() {
+            assert_eq!(size, bytes.len());
+        }
+        assert_eq!(application.decode(&bytes).expect("should decode"), value);
+    }
+
+    #[test]
+    fn primitives_round_trip() {
+        for (type_field, value) in [
+            ("()", DecodedValue::Unit),
+            ("bool", DecodedValue::Bool(true)),
+            ("u8", DecodedValue::U8(7)),
+            ("u16", DecodedValue::U16(300)),
+            ("u32", DecodedValue::U32(70_000)),
+            ("u64", DecodedValue::U64(u64::MAX)),
+            ("u256", DecodedValue::U256([3; 32])),
+            ("b256", DecodedValue::B256([4; 32])),
+            ("str[3]", DecodedValue::String("abc".to_string())),
+        ] {
+            assert_round_trips(&appl("", decl(type_field, vec![])), value);
+        }
+    }
+
+    #[test]
+    fn generic_composite_types_round_trip() {
+        let option = FullTypeDeclaration {
+            type_field: "enum std::option::Option".to_string(),
+            components: vec![
+                appl("None", decl("()", vec![])),
+                appl("Some", decl("generic T", vec![])),
+            ],
+            type_parameters: vec![decl("generic T", vec![])],
+        };
+        let option_of_array = FullTypeApplication {
+            name: "".to_string(),
+            type_decl: option,
+            type_arguments: vec![appl(
+                "",
+                decl("[_; 2]", vec![appl("__array_element", decl("u32", vec![]))]),
+            )],
+        };
+        let a_struct = appl(
+            "",
+            decl(
+                "struct SomeStruct",
+                vec![
+                    appl(
+                        "a",
+                        decl(
+                            "(_, _)",
+                            vec![
+                                appl("__tuple_element", decl("u8", vec![])),
+                                appl("__tuple_element", decl("bool", vec![])),
+                            ],
+                        ),
+                    ),
+                    FullTypeApplication {
+                        name: "b".to_string(),
+                        ..option_of_array.clone()
+                    },
+                ],
+            ),
+        );
+
+        assert_round_trips(
+            &a_struct,
+            DecodedValue::Struct(vec![
+                (
+                    "a".to_string(),
+                    DecodedValue::Tuple(vec![DecodedValue::U8(1), DecodedValue::Bool(false)]),
+                ),
+                (
+                    "b".to_string(),
+                    DecodedValue::Enum {
+                        variant: "Some".to_string(),
+                        value: Box::new(DecodedValue::Array(vec![
+                            DecodedValue::U32(2),
+                            DecodedValue::U32(3),
+                        ])),
+                    },
+                ),
+            ]),
+        );
+
+        let none = DecodedValue::Enum {
+            variant: "None".to_string(),
+            value: Box::new(DecodedValue::Unit),
+        };
+        let bytes = option_of_array.encode(&none).unwrap();
+        assert_eq!(bytes, [0; 8]);
+        assert_eq!(option_of_array.decode(&bytes).unwrap(), none);
+    }
+
+    #[test]
+    fn b256_wrappers_round_trip() {
+        let contract_id = appl(
+            "",
+            decl(
+                "struct std::contract_id::ContractId",
+                vec![appl("bits", decl("b256", vec![]))],
+            ),
+        );
+        let value = DecodedValue::B256([9; 32]);
+
+        let bytes = contract_id.encode(&value).unwrap();
+
+        assert_eq!(bytes, [9; 32]);
+        assert_eq!(contract_id.decode(&bytes).unwrap(), value);
+    }
+
+    #[test]
+    fn configurables_are_encoded_at_their_offsets() {
+        let configurable = |name: &str, offset, type_field| FullConfigurable {
+            name: name.to_string(),
+            application: appl("", decl(type_field, vec![])),
+            offset,
+            indirect: false,
+        };
+        let abi = FullProgramABI {
+            program_type: "predicate".to_string(),
+            configurables: vec![
+                configurable("FLAG", 40, "bool"),
+                configurable("UNSET", 48, "u8"),
+                configurable("AMOUNT", 56, "u64"),
+            ],
+            ..full_abi()
+        };
+        let values = HashMap::from([
+            ("AMOUNT".to_string(), DecodedValue::U64(5)),
+            ("FLAG".to_string(), DecodedValue::Bool(true)),
+        ]);
+
+        assert_eq!(
+            abi.encode_configurables(&values).unwrap(),
+            [(40, vec![1]), (56, vec![0, 0, 0, 0, 0, 0, 0, 5])]
+        );
+
+        let err = abi
+            .encode_configurables(&HashMap::from([(
+                "MISSING".to_string(),
+                DecodedValue::Unit,
+            )]))
+            .expect_err("should have failed");
+        assert_eq!(
+            err.to_string(),
+            "the ABI has no configurable named 'MISSING'"
+        );
+
+        abi.encode_configurables(&HashMap::from([("FLAG".to_string(), DecodedValue::U8(1))]))
+            .expect_err("should have failed");
+    }
+
+    #[test]
+    fn indirect_configurables_are_not_supported() {
+        let abi = FullProgramABI {
+            configurables: vec![FullConfigurable {
+                name: "NAME".to_string(),
+                application: appl("", decl("str", vec![])),
+                offset: 40,
+                indirect: true,
+            }],
+            ..full_abi()
+        };
+
+        let err = abi
+            .encode_configurables(&HashMap::from([(
+                "NAME".to_string(),
+                DecodedValue::String("abc".to_string()),
+            )]))
+            .expect_err("should have failed");
+
+        assert_eq!(
+            err.to_string(),
+            "encoding indirect configurable 'NAME' is not supported"
+        );
+        assert_eq!(abi.encode_configurables(&HashMap::new()).unwrap(), []);
+    }
+
+    #[test]
+    fn mismatched_values_are_rejected() {
+        let u8_type = appl("", decl("u8", vec![]));
+        let a_struct = appl(
+            "",
+            decl("struct SomeStruct", vec![appl("a", decl("u8", vec![]))]),
+        );
+
+        u8_type
+            .encode(&DecodedValue::U64(1))
+            .expect_err("should have failed");
+        a_struct
+            .encode(&DecodedValue::Struct(vec![(
+                "b".to_string(),
+                DecodedValue::U8(1),
+            )]))
+            .expect_err("should have failed");
+        appl("", decl("str[2]", vec![]))
+            .encode(&DecodedValue::String("abc".to_string()))
+            .expect_err("should have failed");
+    }
+}
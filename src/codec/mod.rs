@@ -0,0 +1,132 @@
+//! Encoding and decoding of values following the ABI encoding spec (encoding
+//! version 1). Only statically sized types are supported. Heap types such as `Vec`,
+//! `Bytes` and `String` are rejected with an error.
+
+use std::fmt::{Display, Formatter};
+
+use crate::abi::full_program::FullTypeDeclaration;
+
+mod decoder;
+mod encoder;
+
+/// A value decoded from its ABI encoding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodedValue {
+    Unit,
+    Bool(bool),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    /// Big-endian bytes of a `u256`.
+    U256([u8; 32]),
+    /// A `b256`, or one of the std types wrapping a single `b256`: `Address`,
+    /// `ContractId` and `AssetId`.
+    B256([u8; 32]),
+    String(String),
+    Array(Vec<DecodedValue>),
+    Tuple(Vec<DecodedValue>),
+    Struct(Vec<(String, DecodedValue)>),
+    Enum {
+        variant: String,
+        value: Box<DecodedValue>,
+    },
+}
+
+impl DecodedValue {
+    /// Renders a `B256` as a `0x` prefixed, lowercase hex string of 64 digits.
+    /// Returns `None` for every other value.
+    pub fn to_hex(&self) -> Option<String> {
+        match self {
+            DecodedValue::B256(bytes) => Some(to_hex(bytes)),
+            _ => None,
+        }
+    }
+}
+
+impl Display for DecodedValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecodedValue::Unit => write!(f, "()"),
+            DecodedValue::Bool(value) => write!(f, "{value}"),
+            DecodedValue::U8(value) => write!(f, "{value}"),
+            DecodedValue::U16(value) => write!(f, "{value}"),
+            DecodedValue::U32(value) => write!(f, "{value}"),
+            DecodedValue::U64(value) => write!(f, "{value}"),
+            DecodedValue::U256(bytes) | DecodedValue::B256(bytes) => write!(f, "{}", to_hex(bytes)),
+            DecodedValue::String(value) => write!(f, "{value:?}"),
+            DecodedValue::Array(elements) => {
+                write!(f, "[{}]", itertools::join(elements, ", "))
+            }
+            DecodedValue::Tuple(elements) => {
+                write!(f, "({})", itertools::join(elements, ", "))
+            }
+            DecodedValue::Struct(fields) => {
+                let fields = fields
+                    .iter()
+                    .map(|(name, value)| format!("{name}: {value}"));
+                write!(f, "{{ {} }}", itertools::join(fields, ", "))
+            }
+            DecodedValue::Enum { variant, value } => write!(f, "{variant}({value})"),
+        }
+    }
+}
+
+fn to_hex(bytes: &[u8; 32]) -> String {
+    let digits: String = bytes.iter().map(|byte| format!("{byte:02x}")).collect();
+    format!("0x{digits}")
+}
+
+/// Whether `type_decl` is one of the std structs wrapping a single `b256`, which are
+/// represented as [`DecodedValue::B256`].
+fn is_b256_wrapper(type_decl: &FullTypeDeclaration) -> bool {
+    type_decl.is_struct_type()
+        && type_decl.components.len() == 1
+        && type_decl.components[0].type_decl.type_field == "b256"
+        && type_decl.custom_type_path().is_ok_and(|path| {
+            matches!(
+                path.to_string().as_str(),
+                "std::address::Address" | "std::contract_id::ContractId" | "std::asset_id::AssetId"
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn b256_is_rendered_as_prefixed_hex() {
+        let mut bytes = [0; 32];
+        bytes[0] = 0xab;
+        bytes[31] = 0x01;
+        let value = DecodedValue::B256(bytes);
+
+        let expected = format!("0xab{}01", "0".repeat(60));
+        assert_eq!(value.to_hex().unwrap(), expected);
+        assert_eq!(value.to_string(), expected);
+        assert_eq!(DecodedValue::U64(1).to_hex(), None);
+    }
+
+    #[test]
+    fn composite_values_are_displayed() {
+        let value = DecodedValue::Struct(vec![
+            (
+                "a".to_string(),
+                DecodedValue::Array(vec![DecodedValue::U8(1), DecodedValue::U8(2)]),
+            ),
+            (
+                "b".to_string(),
+                DecodedValue::Enum {
+                    variant: "Some".to_string(),
+                    value: Box::new(DecodedValue::Tuple(vec![
+                        DecodedValue::Bool(true),
+                        DecodedValue::String("hi".to_string()),
+                    ])),
+                },
+            ),
+        ]);
+
+        assert_eq!(value.to_string(), r#"{ a: [1, 2], b: Some((true, "hi")) }"#);
+    }
+}
@@ -1,49 +1,75 @@
-use std::{
-    fmt::{Debug, Display, Formatter},
-    io,
-};
+use std::io;
 
-pub struct Error(pub String);
+use crate::abi::program::{ConcreteTypeId, MetadataTypeId};
+
+/// Errors produced while parsing and processing an ABI.
+///
+/// Revert codes have their own error type, see
+/// [`RevertCodeError`](crate::error_codes::RevertCodeError).
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// The underlying [`serde_json::Error`] is available through
+    /// [`source`](std::error::Error::source) for callers needing its line and column.
+    #[error("failed to parse JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("type with id {0} is not declared in the ABI")]
+    MissingType(usize),
+    #[error("concrete type id '{0}' is not declared")]
+    MissingConcreteType(ConcreteTypeId),
+    #[error("concrete type id '{0}' is declared more than once")]
+    DuplicateConcreteType(ConcreteTypeId),
+    #[error("metadata type id '{0}' is declared more than once")]
+    DuplicateMetadataType(MetadataTypeId),
+    #[error("invalid version '{0}'")]
+    InvalidVersion(String),
+    /// A type that contains itself, by its type field. Unlike a
+    /// [`TypePath`](crate::utils::TypePath), which only names structs and enums, the type
+    /// field can name any of the types a malformed ABI can make refer to itself.
+    #[error("type '{0}' refers to itself")]
+    RecursiveType(String),
+    #[cfg(feature = "bincode")]
+    #[error("failed to encode or decode binary ABI: {0}")]
+    Bincode(#[from] bincode::Error),
+    #[error("{0}")]
+    Other(String),
+}
 
 impl Error {
     pub fn combine<T: Into<Self>>(self, err: T) -> Self {
-        error!("{} {}", self.0, err.into().0)
+        error!("{} {}", self, err.into())
     }
 }
 
 #[macro_export]
 macro_rules! error {
-   ($fmt_str: literal $(,$arg: expr)*) => {$crate::error::Error(format!($fmt_str,$($arg),*))}
+   ($fmt_str: literal $(,$arg: expr)*) => {$crate::error::Error::Other(format!($fmt_str,$($arg),*))}
 }
 
 pub use error;
 
 pub type Result<T> = std::result::Result<T, Error>;
 
-impl Debug for Error {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self.0)
-    }
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-impl Display for Error {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
+    #[test]
+    fn failure_kinds_can_be_matched_on() {
+        let json_err = serde_json::from_str::<serde_json::Value>("{").unwrap_err();
+
+        assert!(matches!(Error::from(json_err), Error::Json(_)));
+        assert!(matches!(error!("some error"), Error::Other(_)));
     }
-}
 
-impl std::error::Error for Error {}
-
-macro_rules! impl_from {
-    ($($err_type:ty),*) => {
-        $(
-            impl From<$err_type> for self::Error {
-                fn from(err: $err_type) -> Self {
-                    Self(err.to_string())
-                }
-            }
-        )*
+    #[test]
+    fn errors_can_be_combined() {
+        let err = error!("first").combine(Error::MissingType(1));
+
+        assert_eq!(
+            err.to_string(),
+            "first type with id 1 is not declared in the ABI"
+        );
     }
 }
-
-impl_from!(serde_json::Error, io::Error);
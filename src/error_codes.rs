@@ -40,14 +40,14 @@ pub enum ErrorSignal {
 }
 
 #[derive(Error, Debug)]
-pub enum Error {
+pub enum RevertCodeError {
     #[error("Unknown revert code: {0}")]
     UnknownRevertCode(u64),
 }
 
 impl ErrorSignal {
     /// Creates a new `ErrorSignal` from provided `revert_code`.
-    pub fn try_from_revert_code(revert_code: u64) -> Result<Self, Error> {
+    pub fn try_from_revert_code(revert_code: u64) -> Result<Self, RevertCodeError> {
         if revert_code == FAILED_REQUIRE_SIGNAL {
             Ok(Self::Require)
         } else if revert_code == FAILED_TRANSFER_TO_ADDRESS_SIGNAL {
@@ -63,7 +63,7 @@ impl ErrorSignal {
         } else if revert_code == REVERT_WITH_LOG_SIGNAL {
             Ok(Self::RevertWithLog)
         } else {
-            Err(Error::UnknownRevertCode(revert_code))
+            Err(RevertCodeError::UnknownRevertCode(revert_code))
         }
     }
 
@@ -81,3 +81,34 @@ impl ErrorSignal {
         }
     }
 }
+
+impl TryFrom<u64> for ErrorSignal {
+    type Error = RevertCodeError;
+
+    fn try_from(revert_code: u64) -> Result<Self, Self::Error> {
+        ErrorSignal::try_from_revert_code(revert_code)
+    }
+}
+
+impl From<ErrorSignal> for u64 {
+    fn from(signal: ErrorSignal) -> Self {
+        signal.to_revert_code()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signals_convert_to_and_from_revert_codes() {
+        let signal = ErrorSignal::try_from(FAILED_ASSERT_EQ_SIGNAL).unwrap();
+        assert!(matches!(signal, ErrorSignal::AssertEq));
+        assert_eq!(u64::from(signal), FAILED_ASSERT_EQ_SIGNAL);
+
+        let err = ErrorSignal::try_from(42).expect_err("should have failed");
+        assert!(matches!(err, RevertCodeError::UnknownRevertCode(42)));
+        let result: Result<ErrorSignal, _> = REVERT_WITH_LOG_SIGNAL.try_into();
+        assert!(matches!(result, Ok(ErrorSignal::RevertWithLog)));
+    }
+}
@@ -1,4 +1,8 @@
 pub mod abi;
+pub mod codec;
 pub mod error;
 pub mod error_codes;
 pub mod utils;
+
+#[cfg(test)]
+mod test_utils;
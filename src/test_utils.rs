@@ -0,0 +1,37 @@
+//! Builders shared by the unit tests of the crate.
+
+use crate::abi::full_program::{FullProgramABI, FullTypeApplication, FullTypeDeclaration};
+
+/// A declaration of a non-generic type.
+pub(crate) fn decl(type_field: &str, components: Vec<FullTypeApplication>) -> FullTypeDeclaration {
+    FullTypeDeclaration {
+        type_field: type_field.to_string(),
+        components,
+        type_parameters: vec![],
+    }
+}
+
+/// An application of `type_decl` without type arguments.
+pub(crate) fn appl(name: &str, type_decl: FullTypeDeclaration) -> FullTypeApplication {
+    FullTypeApplication {
+        name: name.to_string(),
+        type_decl,
+        type_arguments: vec![],
+    }
+}
+
+/// An ABI of a contract without any items, meant to be filled in with struct update
+/// syntax, e.g. `FullProgramABI { functions, ..full_abi() }`.
+pub(crate) fn full_abi() -> FullProgramABI {
+    FullProgramABI {
+        program_type: "contract".to_string(),
+        spec_version: "1".into(),
+        encoding_version: "1".into(),
+        types: vec![],
+        functions: vec![],
+        logged_types: vec![],
+        configurables: vec![],
+        messages_types: vec![],
+        storage_slots: vec![],
+    }
+}
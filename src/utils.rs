@@ -171,7 +171,80 @@ impl ToTokens for TypePath {
 ///
 /// * `type_name`: `type_name` field from [`TypeDeclaration`]( `crate::program_abi::TypeDeclaration` )
 pub fn has_tuple_format(type_name: &str) -> bool {
-    type_name.starts_with('(') && type_name.ends_with(')')
+    if !type_name.starts_with('(') || !type_name.ends_with(')') {
+        return false;
+    }
+
+    // The opening parenthesis must be closed by the last character, with every
+    // parenthesis in between balanced.
+    let mut depth = 0usize;
+    for (position, c) in type_name.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return position == type_name.len() - 1;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    false
+}
+
+/// The regex used by [`extract_generic_name`]. The generic's name is captured by the
+/// first group.
+pub fn generic_name_regex() -> &'static Regex {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"^\s*generic\s+(\S+)\s*$").unwrap();
+    }
+    &RE
+}
+
+/// The regex used by [`try_extract_array_len`]. The array's length is captured by the
+/// first group.
+pub fn array_len_regex() -> &'static Regex {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"(?s)^\s*\[.+;\s*(\d+)\s*\]\s*$").unwrap();
+    }
+    &RE
+}
+
+/// The regex used by [`extract_array_len_kind`]. The array's length, either a number or
+/// the name of a constant, is captured by the first group.
+pub fn array_len_kind_regex() -> &'static Regex {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"(?s)^\s*\[.+;\s*([^\s\]]+)\s*\]\s*$").unwrap();
+    }
+    &RE
+}
+
+/// Matches the names constants can be given, as used for array lengths.
+fn const_name_regex() -> &'static Regex {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"^[A-Za-z_][A-Za-z0-9_]*$").unwrap();
+    }
+    &RE
+}
+
+/// The regex used by [`try_extract_str_len`]. The string's length is captured by the
+/// first group.
+pub fn str_len_regex() -> &'static Regex {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"^\s*str\s*\[\s*(\d+)\s*\]\s*$").unwrap();
+    }
+    &RE
+}
+
+/// The regex used by [`extract_custom_type_name`]. The type's name is captured by the
+/// first group.
+pub fn custom_type_name_regex() -> &'static Regex {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"\s*(?:struct|enum)\s*([^\s<]*)").unwrap();
+    }
+    &RE
 }
 
 /// If `type_name` contains a generic parameter, it will be returned.
@@ -180,10 +253,8 @@ pub fn has_tuple_format(type_name: &str) -> bool {
 ///
 /// * `type_name`: `type_name` field from [`TypeDeclaration`]( `crate::program_abi::TypeDeclaration` )
 pub fn extract_generic_name(type_name: &str) -> Option<String> {
-    lazy_static! {
-        static ref RE: Regex = Regex::new(r"^\s*generic\s+(\S+)\s*$").unwrap();
-    }
-    RE.captures(type_name)
+    generic_name_regex()
+        .captures(type_name)
         .map(|captures| String::from(&captures[1]))
 }
 
@@ -192,17 +263,65 @@ pub fn extract_generic_name(type_name: &str) -> Option<String> {
 /// # Arguments
 ///
 /// * `type_name`: `type_name` field from [`TypeDeclaration`]( `crate::program_abi::TypeDeclaration` )
+///
+/// # Panics
+///
+/// If the length doesn't fit into a `usize`. Use [`try_extract_array_len`] when
+/// dealing with untrusted ABIs.
 pub fn extract_array_len(type_name: &str) -> Option<usize> {
-    lazy_static! {
-        static ref RE: Regex = Regex::new(r"^\s*\[.+;\s*(\d+)\s*\]\s*$").unwrap();
-    }
-    RE.captures(type_name)
-        .map(|captures| captures[1].to_string())
-        .map(|length: String| {
-            length.parse::<usize>().unwrap_or_else(|_| {
-                panic!("Could not extract array length from {length}! Original field {type_name}")
+    try_extract_array_len(type_name).unwrap_or_else(|err| panic!("{err}"))
+}
+
+/// Same as [`extract_array_len`] but returns an error instead of panicking if the
+/// length doesn't fit into a `usize`.
+///
+/// # Arguments
+///
+/// * `type_name`: `type_name` field from [`TypeDeclaration`]( `crate::program_abi::TypeDeclaration` )
+pub fn try_extract_array_len(type_name: &str) -> Result<Option<usize>> {
+    array_len_regex()
+        .captures(type_name)
+        .map(|captures| {
+            let length = &captures[1];
+            length.parse::<usize>().map_err(|_| {
+                error!("Could not extract array length from {length}! Original field {type_name}")
             })
         })
+        .transpose()
+}
+
+/// The length of an array type.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ArrayLen {
+    Literal(usize),
+    /// A symbolic length referring to a const generic parameter.
+    Const(String),
+}
+
+/// If `type_name` represents an Array, its length will be returned. Unlike
+/// [`extract_array_len`], symbolic lengths such as the `N` in `[_; N]` are supported.
+///
+/// # Arguments
+///
+/// * `type_name`: `type_name` field from [`TypeDeclaration`]( `crate::program_abi::TypeDeclaration` )
+pub fn extract_array_len_kind(type_name: &str) -> Result<Option<ArrayLen>> {
+    let Some(captures) = array_len_kind_regex().captures(type_name) else {
+        return Ok(None);
+    };
+
+    let length = &captures[1];
+    if length.chars().all(|c| c.is_ascii_digit()) {
+        let length = length.parse::<usize>().map_err(|_| {
+            error!("Could not extract array length from {length}! Original field {type_name}")
+        })?;
+        Ok(Some(ArrayLen::Literal(length)))
+    } else if const_name_regex().is_match(length) {
+        Ok(Some(ArrayLen::Const(length.to_string())))
+    } else {
+        Err(error!(
+            "Invalid array length {length}! Original field {type_name}"
+        ))
+    }
 }
 
 /// If `type_name` represents a string, its size will be returned;
@@ -210,35 +329,83 @@ pub fn extract_array_len(type_name: &str) -> Option<usize> {
 /// # Arguments
 ///
 /// * `type_name`: `type_name` field from [`TypeDeclaration`]( `crate::program_abi::TypeDeclaration` )
+///
+/// # Panics
+///
+/// If the length doesn't fit into a `usize`. Use [`try_extract_str_len`] when
+/// dealing with untrusted ABIs.
 pub fn extract_str_len(type_name: &str) -> Option<usize> {
-    lazy_static! {
-        static ref RE: Regex = Regex::new(r"^\s*str\s*\[\s*(\d+)\s*\]\s*$").unwrap();
-    }
-    RE.captures(type_name)
-        .map(|captures| captures[1].to_string())
-        .map(|length: String| {
-            length.parse::<usize>().unwrap_or_else(|_| {
-                panic!(
+    try_extract_str_len(type_name).unwrap_or_else(|err| panic!("{err}"))
+}
+
+/// Same as [`extract_str_len`] but returns an error instead of panicking if the
+/// length doesn't fit into a `usize`.
+///
+/// # Arguments
+///
+/// * `type_name`: `type_name` field from [`TypeDeclaration`]( `crate::program_abi::TypeDeclaration` )
+pub fn try_extract_str_len(type_name: &str) -> Result<Option<usize>> {
+    str_len_regex()
+        .captures(type_name)
+        .map(|captures| {
+            let length = &captures[1];
+            length.parse::<usize>().map_err(|_| {
+                error!(
                     "Could not extract string length from {length}! Original field '{type_name}'"
                 )
             })
         })
+        .transpose()
 }
 
-/// If `type_name` represents a custom type, its name will be returned.
+/// If `type_name` represents a custom type, its name will be returned. Inline generic
+/// arguments aren't part of the name, e.g. `struct Foo<T>` yields `Foo`.
 ///
 /// # Arguments
 ///
 /// * `type_name`: `type_name` field from [`TypeDeclaration`]( `crate::program_abi::TypeDeclaration` )
 pub fn extract_custom_type_name(type_field: &str) -> Option<String> {
-    lazy_static! {
-        static ref RE: Regex = Regex::new(r"\s*(?:struct|enum)\s*(\S*)").unwrap();
-    }
-
-    RE.captures(type_field)
+    custom_type_name_regex()
+        .captures(type_field)
         .map(|captures| String::from(&captures[1]))
 }
 
+/// The name the compiler gives to each element of a tuple type's components.
+pub const TUPLE_ELEMENT_NAME: &str = "__tuple_element";
+
+/// The name the compiler gives to the element component of an array type.
+pub const ARRAY_ELEMENT_NAME: &str = "__array_element";
+
+/// Whether `name` is one of the names the compiler gives to components that aren't
+/// named in the source, see [`TUPLE_ELEMENT_NAME`] and [`ARRAY_ELEMENT_NAME`].
+pub fn is_synthetic_component_name(name: &str) -> bool {
+    name == TUPLE_ELEMENT_NAME || name == ARRAY_ELEMENT_NAME
+}
+
+const KNOWN_PRIMITIVE_TYPE_FIELDS: &[&str] = &[
+    "()",
+    "bool",
+    "u8",
+    "u16",
+    "u32",
+    "u64",
+    "u256",
+    "b256",
+    "raw untyped ptr",
+    "raw untyped slice",
+];
+
+/// The `type` fields of the primitive types this crate knows about. Arrays, string
+/// arrays, tuples and custom types are described by patterns instead and aren't listed.
+pub fn known_primitive_type_fields() -> &'static [&'static str] {
+    KNOWN_PRIMITIVE_TYPE_FIELDS
+}
+
+/// Whether `type_field` is one of [`known_primitive_type_fields`].
+pub fn is_known_primitive(type_field: &str) -> bool {
+    KNOWN_PRIMITIVE_TYPE_FIELDS.contains(&type_field)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -445,6 +612,118 @@ mod tests {
         assert_eq!(str_len, 10);
     }
 
+    #[test]
+    fn literal_and_const_array_lens_extracted() {
+        assert_eq!(
+            extract_array_len_kind(" [ _ ; 8 ] ").unwrap(),
+            Some(ArrayLen::Literal(8))
+        );
+        assert_eq!(
+            extract_array_len_kind("[_; N]").unwrap(),
+            Some(ArrayLen::Const("N".to_string()))
+        );
+        assert_eq!(
+            extract_array_len_kind("[[u8; 2]; LEN]").unwrap(),
+            Some(ArrayLen::Const("LEN".to_string()))
+        );
+        assert_eq!(extract_array_len_kind("u64").unwrap(), None);
+        assert_eq!(extract_array_len("[_; N]"), None);
+
+        extract_array_len_kind("[_; 1N]").expect_err("should have failed");
+    }
+
+    #[test]
+    fn array_len_overflow_is_an_error() {
+        let type_name = "[_; 99999999999999999999999999]";
+
+        let err = try_extract_array_len(type_name).expect_err("Should have failed");
+
+        assert_eq!(
+            err.to_string(),
+            "Could not extract array length from 99999999999999999999999999! Original field [_; 99999999999999999999999999]"
+        );
+    }
+
+    #[test]
+    fn str_len_overflow_is_an_error() {
+        let type_name = "str[99999999999999999999999999]";
+
+        let err = try_extract_str_len(type_name).expect_err("Should have failed");
+
+        assert_eq!(
+            err.to_string(),
+            "Could not extract string length from 99999999999999999999999999! Original field 'str[99999999999999999999999999]'"
+        );
+    }
+
+    #[test]
+    fn array_len_is_extracted_whatever_the_element_type() {
+        let multi_line = "[struct Foo<\n    u64\n>; 3]";
+
+        assert_eq!(try_extract_array_len(multi_line).unwrap(), Some(3));
+        assert_eq!(
+            extract_array_len_kind(multi_line).unwrap(),
+            Some(ArrayLen::Literal(3))
+        );
+        assert_eq!(
+            try_extract_array_len("[(u8, [u16; 2]); 4]").unwrap(),
+            Some(4)
+        );
+    }
+
+    #[test]
+    fn try_extract_returns_none_for_other_types() {
+        assert!(try_extract_array_len("u64").unwrap().is_none());
+        assert!(try_extract_str_len("u64").unwrap().is_none());
+    }
+
+    #[test]
+    fn regexes_can_be_reused() {
+        let captures = array_len_regex().captures("[u8; 4]").unwrap();
+        assert_eq!(&captures[1], "4");
+        assert_eq!(&str_len_regex().captures("str[3]").unwrap()[1], "3");
+        assert_eq!(&generic_name_regex().captures("generic T").unwrap()[1], "T");
+        assert_eq!(
+            &custom_type_name_regex().captures("struct Foo").unwrap()[1],
+            "Foo"
+        );
+        assert!(!array_len_regex().is_match("(u8, u8)"));
+        assert_eq!(
+            &array_len_kind_regex().captures("[u8; LEN]").unwrap()[1],
+            "LEN"
+        );
+    }
+
+    #[test]
+    fn tuple_format_requires_balanced_parentheses() {
+        assert!(has_tuple_format("()"));
+        assert!(has_tuple_format("(_, _)"));
+        assert!(has_tuple_format("(u8, (u16, u32))"));
+        assert!(has_tuple_format("((u8, u16), [u32; 2])"));
+
+        assert!(!has_tuple_format("(u8"));
+        assert!(!has_tuple_format("(u8))"));
+        assert!(!has_tuple_format("(u8) (u16)"));
+        assert!(!has_tuple_format("((u8, u16)"));
+        assert!(!has_tuple_format("u8"));
+    }
+
+    #[test]
+    fn synthetic_component_names_are_recognized() {
+        assert!(is_synthetic_component_name("__tuple_element"));
+        assert!(is_synthetic_component_name("__array_element"));
+        assert!(!is_synthetic_component_name("element"));
+    }
+
+    #[test]
+    fn known_primitives_are_recognized() {
+        assert!(known_primitive_type_fields().contains(&"raw untyped slice"));
+        assert!(is_known_primitive("u64"));
+        assert!(is_known_primitive("()"));
+        assert!(!is_known_primitive("str[4]"));
+        assert!(!is_known_primitive("struct u64"));
+    }
+
     #[test]
     fn custom_struct_type_name_extracted() {
         let type_name = "  struct   SomeStruct ";
@@ -462,4 +741,13 @@ mod tests {
 
         assert_eq!(enum_name, "SomeEnum");
     }
+
+    #[test]
+    fn custom_type_name_excludes_inline_generics() {
+        assert_eq!(extract_custom_type_name("struct Foo<T>").unwrap(), "Foo");
+        assert_eq!(
+            extract_custom_type_name("enum std::option::Option<u64>").unwrap(),
+            "std::option::Option"
+        );
+    }
 }